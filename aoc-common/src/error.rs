@@ -0,0 +1,193 @@
+//! The error type shared by the parsers and solvers for every day.
+
+use std::fmt;
+
+
+/// Something wrong with a puzzle input.
+///
+/// Lines and columns count from 1. A parser for a single line doesn't know
+/// which line it was given, so it reports line 0 and the caller fills in the
+/// real line number with [`AocError::at_line`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AocError {
+    /// Some text that should have been a number.
+    InvalidNumber {
+        /// The line the text is on.
+        line: usize,
+        /// The column the text starts at.
+        column: usize,
+        /// The offending text.
+        text: String,
+    },
+    /// Some text that isn't what should be at this point in the input.
+    Unexpected {
+        /// The line the text is on.
+        line: usize,
+        /// The column the text starts at.
+        column: usize,
+        /// The offending text.
+        text: String,
+        /// A description of what should have been there.
+        expected: String,
+    },
+    /// A line ended before something it should contain.
+    Missing {
+        /// The line that is too short.
+        line: usize,
+        /// The column just past the end of the line.
+        column: usize,
+        /// A description of what should have been there.
+        expected: String,
+    },
+    /// The input is well formed but the puzzle has no answer for it.
+    Unsolvable {
+        /// Why there is no answer.
+        reason: String,
+    },
+}
+
+
+/// The column (counting characters from 1) at which `part` starts in `line`.
+///
+/// `part` must be a slice of `line`, as returned by `split`, `trim` and
+/// friends. Anything else gives 0, meaning the column isn't known.
+pub fn column_of(line: &str, part: &str) -> usize {
+    let start = line.as_ptr() as usize;
+    let part_start = part.as_ptr() as usize;
+    if part_start < start || part_start > start + line.len() {
+        return 0;
+    }
+    line[..part_start - start].chars().count() + 1
+}
+
+
+impl AocError {
+    /// `text`, a slice of `line`, should have been a number.
+    pub fn invalid_number(line: &str, text: &str) -> Self {
+        AocError::InvalidNumber {
+            line: 0,
+            column: column_of(line, text),
+            text: text.to_string(),
+        }
+    }
+
+    /// `text`, a slice of `line`, should have been `expected`.
+    pub fn unexpected(line: &str, text: &str, expected: impl Into<String>) -> Self {
+        AocError::Unexpected {
+            line: 0,
+            column: column_of(line, text),
+            text: text.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// `line` ended before we found `expected`.
+    pub fn missing(line: &str, expected: impl Into<String>) -> Self {
+        AocError::Missing {
+            line: 0,
+            column: line.chars().count() + 1,
+            expected: expected.into(),
+        }
+    }
+
+    /// The puzzle has no answer for this input.
+    pub fn unsolvable(reason: impl Into<String>) -> Self {
+        AocError::Unsolvable { reason: reason.into() }
+    }
+
+    /// The line the error is on, or 0 if it isn't known.
+    pub fn line(&self) -> usize {
+        match self {
+            AocError::InvalidNumber { line, .. } |
+            AocError::Unexpected { line, .. } |
+            AocError::Missing { line, .. } => *line,
+            AocError::Unsolvable { .. } => 0,
+        }
+    }
+
+    /// The column the error is at, or 0 if it isn't known.
+    pub fn column(&self) -> usize {
+        match self {
+            AocError::InvalidNumber { column, .. } |
+            AocError::Unexpected { column, .. } |
+            AocError::Missing { column, .. } => *column,
+            AocError::Unsolvable { .. } => 0,
+        }
+    }
+
+    /// Set the line number, if it isn't already known.
+    pub fn at_line(mut self, line_number: usize) -> Self {
+        match &mut self {
+            AocError::InvalidNumber { line, .. } |
+            AocError::Unexpected { line, .. } |
+            AocError::Missing { line, .. } => {
+                if *line == 0 {
+                    *line = line_number;
+                }
+            }
+            AocError::Unsolvable { .. } => {}
+        }
+        self
+    }
+
+    /// Adjust the column of an error found while parsing `inner`, a slice of
+    /// `outer`, so that it is relative to `outer` instead.
+    pub fn within(mut self, outer: &str, inner: &str) -> Self {
+        let offset = column_of(outer, inner);
+        match &mut self {
+            AocError::InvalidNumber { column, .. } |
+            AocError::Unexpected { column, .. } |
+            AocError::Missing { column, .. } => {
+                if *column > 0 && offset > 0 {
+                    *column += offset - 1;
+                }
+            }
+            AocError::Unsolvable { .. } => {}
+        }
+        self
+    }
+}
+
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line() > 0 {
+            write!(f, "line {}, column {}: ", self.line(), self.column())?;
+        } else if self.column() > 0 {
+            write!(f, "column {}: ", self.column())?;
+        }
+        match self {
+            AocError::InvalidNumber { text, .. } => write!(f, "expected a number, found `{}`", text),
+            AocError::Unexpected { text, expected, .. } => write!(f, "expected {}, found `{}`", expected, text),
+            AocError::Missing { expected, .. } => write!(f, "expected {}, found end of line", expected),
+            AocError::Unsolvable { reason } => write!(f, "no solution: {}", reason),
+        }
+    }
+}
+
+
+impl std::error::Error for AocError {}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn columns_are_relative_to_the_outer_line() {
+        let line = "Game 12: 3 blue, x red";
+        let selection = &line[9..];
+        let error = AocError::invalid_number(selection, &selection[8..9]);
+        assert_eq!(error.column(), 9);
+        let error = error.within(line, selection).at_line(4);
+        assert_eq!(error, AocError::InvalidNumber { line: 4, column: 18, text: "x".to_string() });
+        assert_eq!(error.to_string(), "line 4, column 18: expected a number, found `x`");
+    }
+
+    #[test]
+    fn at_line_keeps_a_known_line() {
+        let error = AocError::missing("abc", "`:`").at_line(2).at_line(7);
+        assert_eq!(error.line(), 2);
+        assert_eq!(error.column(), 4);
+    }
+}
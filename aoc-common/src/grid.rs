@@ -0,0 +1,293 @@
+//! A rectangular grid of cells, as used by the puzzles drawn as a map.
+//!
+//! Cells are stored row by row in a single `Vec`. Coordinates are signed so
+//! that stepping off the edge of the grid gives a point for which access
+//! returns `None`, rather than an underflow.
+
+use std::fmt;
+use std::ops::{Add, Index, IndexMut};
+use std::str::FromStr;
+
+use crate::parse::numbered_lines;
+use crate::AocError;
+
+
+/// A position in a [`Grid`], with `y` counting down the rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point {
+    /// The column, counting from 0 at the left.
+    pub x: i64,
+    /// The row, counting from 0 at the top.
+    pub y: i64,
+}
+
+
+impl Point {
+    /// The point at column `x` of row `y`.
+    pub const fn new(x: i64, y: i64) -> Self {
+        Point { x, y }
+    }
+}
+
+
+impl Add for Point {
+    type Output = Point;
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+
+/// The steps to the four cells sharing an edge with a cell: up, right, down
+/// and left.
+pub const DIRECTIONS_4: [Point; 4] = [
+    Point::new(0, -1),
+    Point::new(1, 0),
+    Point::new(0, 1),
+    Point::new(-1, 0),
+];
+
+
+/// The steps to the eight cells sharing an edge or a corner with a cell,
+/// clockwise from up-left.
+pub const DIRECTIONS_8: [Point; 8] = [
+    Point::new(-1, -1),
+    Point::new(0, -1),
+    Point::new(1, -1),
+    Point::new(1, 0),
+    Point::new(1, 1),
+    Point::new(0, 1),
+    Point::new(-1, 1),
+    Point::new(-1, 0),
+];
+
+
+/// A rectangular grid of cells of type `T`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+
+impl<T> Grid<T> {
+    /// A grid made from `cells` laid out row by row, `width` cells to a row.
+    ///
+    /// # Panics
+    ///
+    /// If the number of cells isn't a multiple of `width`.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        assert!(width > 0 || cells.is_empty(), "a grid with cells must have a width");
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        assert_eq!(width * height, cells.len(), "the cells don't fill a whole number of rows");
+        Grid { width, height, cells }
+    }
+
+    /// A grid of `width` by `height` cells all holding `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid { width, height, cells: vec![value; width * height] }
+    }
+
+    /// The number of cells in each row.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `point` is inside the grid.
+    pub fn contains(&self, point: Point) -> bool {
+        self.index_of(point).is_some()
+    }
+
+    fn index_of(&self, point: Point) -> Option<usize> {
+        let x = usize::try_from(point.x).ok().filter(|x| *x < self.width)?;
+        let y = usize::try_from(point.y).ok().filter(|y| *y < self.height)?;
+        Some(y * self.width + x)
+    }
+
+    fn point_of(&self, index: usize) -> Point {
+        Point::new((index % self.width) as i64, (index / self.width) as i64)
+    }
+
+    /// The cell at `point`, or `None` if that is outside the grid.
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|i| &self.cells[i])
+    }
+
+    /// The cell at `point` for changing, or `None` if that is outside the
+    /// grid.
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index_of(point).map(|i| &mut self.cells[i])
+    }
+
+    /// Every point in the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.cells.len()).map(|i| self.point_of(i))
+    }
+
+    /// Every cell in the grid with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().enumerate().map(|(i, cell)| (self.point_of(i), cell))
+    }
+
+    /// The cells of row `y`, or `None` if there is no such row.
+    pub fn row(&self, y: i64) -> Option<&[T]> {
+        let y = usize::try_from(y).ok().filter(|y| *y < self.height)?;
+        Some(&self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    /// Every row of the grid, from the top.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks() doesn't allow a size of 0, which an empty grid would have
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The cells of column `x` from the top, which is empty if there is no
+    /// such column.
+    pub fn column(&self, x: i64) -> impl Iterator<Item = &T> {
+        let (start, len) = match usize::try_from(x) {
+            Ok(x) if x < self.width => (x, self.height),
+            _ => (0, 0),
+        };
+        self.cells[start..].iter().step_by(self.width.max(1)).take(len)
+    }
+
+    /// The cells in the rectangle with corners `top_left` and `bottom_right`
+    /// (both included), clipped to the grid, with their positions.
+    pub fn region(&self, top_left: Point, bottom_right: Point) -> impl Iterator<Item = (Point, &T)> {
+        let x_range = top_left.x.max(0)..=bottom_right.x.min(self.width as i64 - 1);
+        let y_range = top_left.y.max(0)..=bottom_right.y.min(self.height as i64 - 1);
+        y_range.flat_map(move |y| x_range.clone().map(move |x| Point::new(x, y)))
+               .map(|point| (point, &self[point]))
+    }
+
+    /// The points up to four sharing an edge with `point` that are inside
+    /// the grid.
+    pub fn neighbours_4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        DIRECTIONS_4.iter().map(move |step| point + *step).filter(|p| self.contains(*p))
+    }
+
+    /// The points up to eight sharing an edge or a corner with `point` that
+    /// are inside the grid.
+    pub fn neighbours_8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        DIRECTIONS_8.iter().map(move |step| point + *step).filter(|p| self.contains(*p))
+    }
+
+    /// A grid of the same shape with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+}
+
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+    fn index(&self, point: Point) -> &T {
+        self.get(point).unwrap_or_else(|| panic!("{:?} is outside the grid", point))
+    }
+}
+
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point).unwrap_or_else(|| panic!("{:?} is outside the grid", point))
+    }
+}
+
+
+// every row must be the same width as the first
+impl FromStr for Grid<char> {
+    type Err = AocError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut width = None;
+        let mut cells = Vec::new();
+        for (line_number, line) in numbered_lines(s) {
+            let line_width = line.chars().count();
+            let width = *width.get_or_insert(line_width);
+            if line_width < width {
+                let expected = format!("{} characters", width);
+                return Err(AocError::missing(line, expected).at_line(line_number));
+            }
+            if line_width > width {
+                let (extra_start, _) = line.char_indices().nth(width).unwrap();
+                let extra = &line[extra_start..];
+                return Err(AocError::unexpected(line, extra, "end of line").at_line(line_number));
+            }
+            cells.extend(line.chars());
+        }
+        Ok(Grid::from_vec(width.unwrap_or(0), cells))
+    }
+}
+
+
+/// Draws the grid a row to a line, the way puzzle inputs are written.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Grid<char> {
+        "abc\ndef\n".parse().unwrap()
+    }
+
+    #[test]
+    fn access_outside_the_grid_is_none() {
+        let grid = example();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(Point::new(2, 1)), Some(&'f'));
+        assert_eq!(grid.get(Point::new(-1, 0)), None);
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, 2)), None);
+    }
+
+    #[test]
+    fn neighbours_stay_inside_the_grid() {
+        let grid = example();
+        let corner: Vec<Point> = grid.neighbours_8(Point::new(0, 0)).collect();
+        assert_eq!(corner, vec![Point::new(1, 0), Point::new(1, 1), Point::new(0, 1)]);
+        assert_eq!(grid.neighbours_4(Point::new(1, 1)).count(), 3);
+        assert_eq!(grid.neighbours_8(Point::new(1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn rows_columns_and_regions() {
+        let grid = example();
+        assert_eq!(grid.row(1), Some(&['d', 'e', 'f'][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.column(5).count(), 0);
+        let region: String = grid.region(Point::new(1, -1), Point::new(5, 0)).map(|(_, c)| c).collect();
+        assert_eq!(region, "bc");
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+    }
+
+    #[test]
+    fn short_rows_are_an_error() {
+        let result = "abc\n\nde".parse::<Grid<char>>();
+        assert_eq!(result.err(), Some(AocError::Missing {
+            line: 3,
+            column: 3,
+            expected: "3 characters".to_string(),
+        }));
+    }
+}
@@ -0,0 +1,227 @@
+//! Loading puzzle inputs at runtime.
+//!
+//! An input can come from a file, from stdin, or from a directory holding the
+//! inputs for several days. In a directory the input for day 5 is looked for
+//! as `day-05.txt`, then as `day-05/src/input.txt` so that the root of this
+//! repository is itself a valid input directory.
+
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process;
+
+
+/// Where to read a puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// A single input file.
+    File(PathBuf),
+    /// Standard input.
+    Stdin,
+    /// A directory of inputs named after the day they belong to.
+    Dir(PathBuf),
+}
+
+
+/// An input that could not be read.
+#[derive(Debug)]
+pub struct InputError {
+    /// The paths we tried, or empty if we were reading stdin.
+    pub tried: Vec<PathBuf>,
+    /// The error from the last attempt.
+    pub source: io::Error,
+}
+
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.tried.as_slice() {
+            [] => write!(f, "unable to read input from stdin: {}", self.source),
+            [path] => write!(f, "unable to read input file {}: {}", path.display(), self.source),
+            paths => {
+                write!(f, "unable to find input, tried")?;
+                for path in paths {
+                    write!(f, " {}", path.display())?;
+                }
+                write!(f, ": {}", self.source)
+            }
+        }
+    }
+}
+
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+
+impl InputSource {
+    /// Interpret a command line argument: `-` means stdin, an existing
+    /// directory means a directory of inputs and anything else is a file.
+    pub fn from_arg(arg: &str) -> Self {
+        let path = Path::new(arg);
+        if arg == "-" {
+            InputSource::Stdin
+        } else if path.is_dir() {
+            InputSource::Dir(path.to_path_buf())
+        } else {
+            InputSource::File(path.to_path_buf())
+        }
+    }
+
+    /// The files this source would try, in order, for the given day.
+    pub fn candidates(&self, day: u8) -> Vec<PathBuf> {
+        match self {
+            InputSource::File(path) => vec![path.clone()],
+            InputSource::Stdin => vec![],
+            InputSource::Dir(dir) => vec![
+                dir.join(format!("day-{:02}.txt", day)),
+                dir.join(format!("day-{:02}", day)).join("src").join("input.txt"),
+            ],
+        }
+    }
+
+    /// Read the input for the given day.
+    pub fn read(&self, day: u8) -> Result<String, InputError> {
+        if *self == InputSource::Stdin {
+            let mut input = String::new();
+            return match io::stdin().read_to_string(&mut input) {
+                Ok(_) => Ok(input),
+                Err(source) => Err(InputError { tried: vec![], source }),
+            };
+        }
+        let tried = self.candidates(day);
+        let mut last_error = None;
+        for path in &tried {
+            match fs::read_to_string(path) {
+                Ok(input) => return Ok(input),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(InputError {
+            tried,
+            source: last_error.expect("every non-stdin source has at least one candidate"),
+        })
+    }
+}
+
+
+/// The command line of a per-day binary: the options it was given and the
+/// argument naming the input, if there was one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Args {
+    /// The options given, such as `--json`, in order.
+    pub flags: Vec<String>,
+    /// The argument naming the input.
+    pub input: Option<String>,
+}
+
+
+impl Args {
+    /// Sort `args`, which don't include the program name, into options and
+    /// the input. Anything starting with `--` must be one of `known`, and
+    /// there can be at most one input.
+    pub fn parse(args: impl IntoIterator<Item = String>, known: &[&str]) -> Result<Self, String> {
+        let mut parsed = Args::default();
+        for arg in args {
+            if arg.starts_with("--") {
+                if !known.contains(&arg.as_str()) {
+                    return Err(format!("unknown option `{}`", arg));
+                }
+                parsed.flags.push(arg);
+            } else if let Some(input) = &parsed.input {
+                return Err(format!("unexpected argument `{}` after the input `{}`", arg, input));
+            } else {
+                parsed.input = Some(arg);
+            }
+        }
+        Ok(parsed)
+    }
+
+    /// Whether the option `flag` was given.
+    pub fn has(&self, flag: &str) -> bool {
+        self.flags.iter().any(|given| given == flag)
+    }
+}
+
+
+/// Parse the command line of a per-day binary that takes the options in
+/// `known`. Prints the problem and a usage line and exits if it can't be
+/// parsed.
+pub fn args_or_exit(known: &[&str]) -> Args {
+    match Args::parse(std::env::args().skip(1), known) {
+        Ok(args) => args,
+        Err(e) => {
+            let program = std::env::args().next()
+                                          .map(PathBuf::from)
+                                          .and_then(|path| Some(path.file_name()?.to_string_lossy().into_owned()))
+                                          .unwrap_or_default();
+            let options: String = known.iter().map(|flag| format!(" [{}]", flag)).collect();
+            eprintln!("{}\nusage: {}{} [INPUT]", e, program, options);
+            process::exit(2);
+        }
+    }
+}
+
+
+/// Read the input named on the command line, for use by the per-day
+/// binaries that take no options. With no argument this reads
+/// `src/input.txt`, which is where the input lives when running with
+/// `cargo run` in a day's directory. Prints the error and exits if the
+/// command line is wrong or the input can't be read.
+pub fn from_args_or_exit(day: u8) -> String {
+    from_arg_or_exit(day, args_or_exit(&[]).input.as_deref())
+}
+
+
+/// Like [`from_args_or_exit`], for binaries that take options and so parse
+/// the command line with [`args_or_exit`] themselves.
+pub fn from_arg_or_exit(day: u8, arg: Option<&str>) -> String {
+    let source = match arg {
+        Some(arg) => InputSource::from_arg(arg),
+        None => InputSource::File(PathBuf::from("src/input.txt")),
+    };
+    match source.read(day) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_from_directory() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let source = InputSource::from_arg(root.to_str().unwrap());
+        assert_eq!(source, InputSource::Dir(root.clone()));
+        let input = source.read(6).expect("day 6 input should exist");
+        assert!(input.starts_with("Time:"));
+    }
+
+    #[test]
+    fn missing_file_names_the_path() {
+        let source = InputSource::from_arg("no/such/input.txt");
+        let error = source.read(1).unwrap_err();
+        assert_eq!(error.tried, vec![PathBuf::from("no/such/input.txt")]);
+        assert!(error.to_string().starts_with("unable to read input file no/such/input.txt"));
+    }
+
+    #[test]
+    fn unknown_options_are_rejected() {
+        let args = |args: &[&str]| Args::parse(args.iter().map(|arg| arg.to_string()), &["--json"]);
+        let parsed = args(&["--json", "-"]).unwrap();
+        assert!(parsed.has("--json"));
+        assert_eq!(parsed.input.as_deref(), Some("-"));
+        assert_eq!(args(&["--jsn"]), Err("unknown option `--jsn`".to_string()));
+        assert_eq!(args(&["a.txt", "b.txt"]), Err("unexpected argument `b.txt` after the input `a.txt`".to_string()));
+    }
+}
@@ -0,0 +1,39 @@
+//! Code shared between the solutions for each day.
+
+#![warn(missing_docs)]
+
+pub mod error;
+pub mod grid;
+pub mod input;
+pub mod logging;
+pub mod parse;
+pub mod random;
+pub mod table;
+
+pub use error::AocError;
+
+
+/// A solver for one day's puzzle.
+///
+/// Each day crate provides a unit struct implementing this so that the `aoc`
+/// runner can dispatch to it without knowing anything about the puzzle.
+pub trait Solution {
+    /// The day of December this puzzle was released on.
+    fn day(&self) -> u8;
+
+    /// Solve part 1 of the puzzle for the given input.
+    fn part1(&self, input: &str) -> Result<String, AocError>;
+
+    /// Solve part 2 of the puzzle for the given input.
+    fn part2(&self, input: &str) -> Result<String, AocError>;
+
+    /// Solve the given part (1 or 2) of the puzzle, or `None` if there is no
+    /// such part.
+    fn part(&self, part: u8, input: &str) -> Option<Result<String, AocError>> {
+        match part {
+            1 => Some(self.part1(input)),
+            2 => Some(self.part2(input)),
+            _ => None,
+        }
+    }
+}
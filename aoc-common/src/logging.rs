@@ -0,0 +1,64 @@
+//! Logging for the solvers.
+//!
+//! Solvers report what they are doing through the [`log`] macros: `debug!`
+//! for a line or two per part and `trace!` for every item they look at.
+//! Nothing is printed unless a runner calls [`init`], so timings and piped
+//! answers stay clean by default.
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+
+// writes each message to stderr, tagged with its level and the crate it came from
+struct StderrLogger;
+
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("[{} {}] {}", record.level(), record.target(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+
+static LOGGER: StderrLogger = StderrLogger;
+
+
+/// The level to log at for a number of `--verbose` flags: warnings only by
+/// default, then `debug`, then `trace`.
+pub fn level_for_verbosity(verbosity: u8) -> LevelFilter {
+    match verbosity {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    }
+}
+
+
+/// Print messages at `level` and above to stderr.
+pub fn init(level: LevelFilter) {
+    // only the first call can install the logger, but later ones still
+    // change the level
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(level);
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_verbose_flag_shows_more() {
+        assert_eq!(level_for_verbosity(0), LevelFilter::Warn);
+        assert_eq!(level_for_verbosity(1), LevelFilter::Debug);
+        assert_eq!(level_for_verbosity(2), LevelFilter::Trace);
+        assert_eq!(level_for_verbosity(5), LevelFilter::Trace);
+    }
+}
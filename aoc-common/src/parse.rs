@@ -0,0 +1,228 @@
+//! Helpers for the parsing that most days need.
+//!
+//! Problems are reported as an [`AocError`] pointing at the offending text.
+//! Functions given a single line report columns relative to that line and
+//! leave the line number for the caller to fill in; functions given the whole
+//! input fill in line numbers themselves.
+
+use std::str::FromStr;
+
+use crate::AocError;
+
+
+/// The non-blank lines of `input`, each with its line number counting from 1.
+pub fn numbered_lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input.lines()
+         .enumerate()
+         .map(|(line_index, line)| (line_index + 1, line))
+         .filter(|(_, line)| !line.trim().is_empty())
+}
+
+
+/// Parse each non-blank line of `input` with `T::from_str`, adding the line
+/// number to any error.
+pub fn parse_lines<T: FromStr<Err = AocError>>(input: &str) -> Result<Vec<T>, AocError> {
+    numbered_lines(input)
+        .map(|(line_number, line)| line.parse().map_err(|e: AocError| e.at_line(line_number)))
+        .collect()
+}
+
+
+/// Parse `text`, which must be a slice of `line`, as a number of any type.
+pub fn parse_number<T: FromStr>(line: &str, text: &str) -> Result<T, AocError> {
+    text.parse().map_err(|_| AocError::invalid_number(line, text))
+}
+
+
+/// Parse a whitespace separated list of numbers of any type, such as
+/// `-3 14  15`. There must be at least one.
+pub fn parse_number_list<T: FromStr>(s: &str) -> Result<Vec<T>, AocError> {
+    let numbers = s.split_whitespace()
+                   .map(|n| parse_number(s, n))
+                   .collect::<Result<Vec<T>, AocError>>()?;
+    if numbers.is_empty() {
+        Err(AocError::missing(s, "a list of numbers"))
+    } else {
+        Ok(numbers)
+    }
+}
+
+
+/// Parse exactly `N` whitespace separated numbers.
+pub fn parse_number_array<T: FromStr + Copy + Default, const N: usize>(s: &str) -> Result<[T; N], AocError> {
+    let mut numbers = [T::default(); N];
+    let mut fields = s.split_whitespace();
+    for number in numbers.iter_mut() {
+        let Some(field) = fields.next() else {
+            return Err(AocError::missing(s, format!("{} numbers", N)));
+        };
+        *number = parse_number(s, field)?;
+    }
+    if let Some(extra) = fields.next() {
+        return Err(AocError::unexpected(s, extra, "end of line"));
+    }
+    Ok(numbers)
+}
+
+
+/// Split `s` at the first `separator`, such as the `:` in `Game 1: 3 blue`.
+pub fn split_pair(s: &str, separator: char) -> Result<(&str, &str), AocError> {
+    s.split_once(separator)
+     .ok_or_else(|| AocError::missing(s, format!("`{}`", separator)))
+}
+
+
+/// Strip `label`, such as `seeds:`, from the start of `line` and return the
+/// rest of the line.
+pub fn strip_label<'a>(line: &'a str, label: &str) -> Result<&'a str, AocError> {
+    line.strip_prefix(label).ok_or_else(|| {
+        let found = line.split_whitespace().next().unwrap_or(line);
+        AocError::unexpected(line, found, format!("`{}`", label))
+    })
+}
+
+
+/// Parse the numbers following a label, as in `Time:      7  15   30`.
+pub fn parse_labelled_numbers<T: FromStr>(line: &str, label: &str) -> Result<Vec<T>, AocError> {
+    let rest = strip_label(line, label)?;
+    parse_number_list(rest).map_err(|e| e.within(line, rest))
+}
+
+
+/// Parse a key/value header such as `Game 12` or `Card   3`, checking the
+/// key and returning the value.
+pub fn parse_header<T: FromStr>(header: &str, key: &str) -> Result<T, AocError> {
+    let mut fields = header.split_whitespace();
+    match fields.next() {
+        Some(found) if found == key => {}
+        Some(found) => return Err(AocError::unexpected(header, found, format!("`{}`", key))),
+        None => return Err(AocError::missing(header, format!("`{}`", key))),
+    }
+    let Some(value) = fields.next() else {
+        return Err(AocError::missing(header, format!("a value after `{}`", key)));
+    };
+    if let Some(extra) = fields.next() {
+        return Err(AocError::unexpected(header, extra, "end of header"));
+    }
+    parse_number(header, value)
+}
+
+
+/// A run of consecutive non-blank lines, such as one of the maps in the
+/// day 5 almanac. Blocks are never empty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block<'a> {
+    /// The lines in the block, each with its line number counting from 1.
+    pub lines: Vec<(usize, &'a str)>,
+}
+
+
+impl<'a> Block<'a> {
+    /// The first line of the block and its line number.
+    pub fn first(&self) -> (usize, &'a str) {
+        self.lines[0]
+    }
+
+    /// The lines after the first, such as the entries following a header.
+    pub fn rest(&self) -> &[(usize, &'a str)] {
+        &self.lines[1..]
+    }
+}
+
+
+/// Split `input` into blocks of lines separated by blank lines.
+pub fn blocks(input: &str) -> Vec<Block<'_>> {
+    let mut blocks = vec![];
+    let mut current = vec![];
+    for (line_index, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            if !current.is_empty() {
+                blocks.push(Block { lines: current });
+                current = vec![];
+            }
+        } else {
+            current.push((line_index + 1, line));
+        }
+    }
+    if !current.is_empty() {
+        blocks.push(Block { lines: current });
+    }
+    blocks
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Pair(i32, i32);
+
+    impl FromStr for Pair {
+        type Err = AocError;
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let [a, b] = parse_number_array(s)?;
+            Ok(Pair(a, b))
+        }
+    }
+
+    #[test]
+    fn number_lists_of_any_type() {
+        assert_eq!(parse_number_list::<u32>(" 41 48  6"), Ok(vec![41, 48, 6]));
+        assert_eq!(parse_number_list::<i64>("-3 14 -15"), Ok(vec![-3, 14, -15]));
+        assert_eq!(parse_number_list::<u8>("1 256"), Err(AocError::InvalidNumber {
+            line: 0,
+            column: 3,
+            text: "256".to_string(),
+        }));
+        assert_eq!(parse_number_list::<u8>("  "), Err(AocError::Missing {
+            line: 0,
+            column: 3,
+            expected: "a list of numbers".to_string(),
+        }));
+    }
+
+    #[test]
+    fn number_arrays_must_be_the_right_length() {
+        assert_eq!(parse_number_array::<u64, 3>("50 98 2"), Ok([50, 98, 2]));
+        assert_eq!(parse_number_array::<u64, 3>("50 98").unwrap_err().column(), 6);
+        assert_eq!(parse_number_array::<u64, 3>("50 98 2 7"), Err(AocError::Unexpected {
+            line: 0,
+            column: 9,
+            text: "7".to_string(),
+            expected: "end of line".to_string(),
+        }));
+    }
+
+    #[test]
+    fn labels_and_headers() {
+        assert_eq!(parse_labelled_numbers::<u64>("Time:      7  15   30", "Time:"), Ok(vec![7, 15, 30]));
+        assert_eq!(parse_labelled_numbers::<u64>("Time: 7 x", "Time:").unwrap_err().column(), 9);
+        assert_eq!(strip_label("Distnce: 9", "Distance:"), Err(AocError::Unexpected {
+            line: 0,
+            column: 1,
+            text: "Distnce:".to_string(),
+            expected: "`Distance:`".to_string(),
+        }));
+        assert_eq!(parse_header::<usize>("Card   3", "Card"), Ok(3));
+        assert_eq!(parse_header::<usize>("Game 3", "Card").unwrap_err().column(), 1);
+        assert_eq!(split_pair("Game 1: 3 blue", ':'), Ok(("Game 1", " 3 blue")));
+        assert_eq!(split_pair("Game 1", ':').unwrap_err().column(), 7);
+    }
+
+    #[test]
+    fn lines_are_numbered_from_one() {
+        assert_eq!(parse_lines::<Pair>("1 2\n\n-3 4\n"), Ok(vec![Pair(1, 2), Pair(-3, 4)]));
+        assert_eq!(parse_lines::<Pair>("1 2\n\n3 x").unwrap_err().line(), 3);
+    }
+
+    #[test]
+    fn blocks_are_separated_by_blank_lines() {
+        let blocks = blocks("seeds: 1 2\n\na map:\n1 2 3\n4 5 6\n\n\nb map:\n");
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0].first(), (1, "seeds: 1 2"));
+        assert_eq!(blocks[1].first(), (3, "a map:"));
+        assert_eq!(blocks[1].rest(), &[(4, "1 2 3"), (5, "4 5 6")]);
+        assert!(blocks[2].rest().is_empty());
+    }
+}
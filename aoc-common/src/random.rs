@@ -0,0 +1,56 @@
+//! A small seeded pseudo-random number generator, for making up large
+//! inputs such as the ones the benchmarks use. The same seed always gives the
+//! same numbers, so every run sees the same input.
+
+
+/// An xorshift generator. It is quick and repeatable, but far from random
+/// enough for anything that matters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XorShift {
+    state: u64,
+}
+
+
+impl Default for XorShift {
+    fn default() -> Self {
+        XorShift::new(0x2545_f491_4f6c_dd1d)
+    }
+}
+
+
+impl XorShift {
+    /// A generator starting from `seed`, which must not be 0.
+    pub fn new(seed: u64) -> Self {
+        assert_ne!(seed, 0, "an xorshift generator seeded with 0 only gives 0");
+        XorShift { state: seed }
+    }
+
+    /// The next number in the sequence.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    /// A number below `limit`, which must not be 0.
+    pub fn below(&mut self, limit: usize) -> usize {
+        (self.next_u64() % limit as u64) as usize
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_numbers() {
+        let mut random = XorShift::default();
+        let numbers: Vec<usize> = (0..100).map(|_| random.below(6)).collect();
+        assert!(numbers.iter().all(|n| *n < 6));
+        let mut again = XorShift::default();
+        assert_eq!((0..100).map(|_| again.below(6)).collect::<Vec<_>>(), numbers);
+        assert_ne!(XorShift::new(1).next_u64(), XorShift::default().next_u64());
+    }
+}
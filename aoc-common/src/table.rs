@@ -0,0 +1,51 @@
+//! Plain text tables, for showing where an answer comes from.
+
+
+/// Which side of a column its cells line up on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    /// Pad cells on the right, as for text.
+    Left,
+    /// Pad cells on the left, as for numbers.
+    Right,
+}
+
+
+/// Lay out `rows`, usually a heading followed by the data, in columns as
+/// wide as their widest cell with two spaces between them. Every row must
+/// have the same number of cells, and each line has its trailing space
+/// trimmed.
+pub fn format_table<R: AsRef<[String]>>(rows: &[R], align: Align) -> String {
+    let num_columns = rows.first().map_or(0, |row| row.as_ref().len());
+    let widths: Vec<usize> = (0..num_columns)
+        .map(|column| rows.iter().map(|row| row.as_ref()[column].chars().count()).max().unwrap())
+        .collect();
+    let mut table = String::new();
+    for row in rows {
+        let cells: Vec<String> = row.as_ref()
+                                    .iter()
+                                    .zip(&widths)
+                                    .map(|(cell, width)| match align {
+                                        Align::Left => format!("{:<width$}", cell, width = width),
+                                        Align::Right => format!("{:>width$}", cell, width = width),
+                                    })
+                                    .collect();
+        table.push_str(cells.join("  ").trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn columns_line_up() {
+        let rows = [["name", "value"], ["a", "12345"], ["long name", ""]].map(|row| row.map(String::from));
+        assert_eq!(format_table(&rows, Align::Left), "name       value\na          12345\nlong name\n");
+        assert_eq!(format_table(&rows, Align::Right), "     name  value\n        a  12345\nlong name\n");
+        assert_eq!(format_table::<Vec<String>>(&[], Align::Left), "");
+    }
+}
@@ -0,0 +1,227 @@
+use std::process::ExitCode;
+use std::time::Instant;
+
+use aoc_common::input::InputSource;
+use aoc_common::logging;
+use aoc_common::{AocError, Solution};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+
+/// Run the Advent of Code 2023 solutions.
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    /// Log what the solutions are doing to stderr: once for a summary of
+    /// each part, twice for every item they look at.
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    verbose: u8,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run and time the solutions, either for every day or just one.
+    Run {
+        /// Only run this day.
+        #[arg(long)]
+        day: Option<u8>,
+
+        /// Only run this part (1 or 2).
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Where to read the inputs from: a file, `-` for stdin, or a directory
+        /// holding `day-NN.txt` or `day-NN/src/input.txt` for each day.
+        #[arg(long, default_value = ".")]
+        input: String,
+    },
+
+    /// Check each day 5 map for overlapping entries, overflow, and values
+    /// reached twice or not at all.
+    Validate {
+        #[command(flatten)]
+        input: InputArg,
+    },
+
+    /// Show what it takes to win each day 6 race.
+    Races {
+        /// Write JSON instead of tables.
+        #[arg(long)]
+        json: bool,
+
+        #[command(flatten)]
+        input: InputArg,
+    },
+
+    /// Show how each day 7 hand was classified and ranked.
+    Explain {
+        #[command(flatten)]
+        input: InputArg,
+    },
+
+    /// Draw the day 8 network as a graph.
+    Graph {
+        /// The kind of graph to write.
+        #[arg(long, value_enum, default_value_t = GraphFormat::Dot)]
+        format: GraphFormat,
+
+        /// Pick out the starts, ends and part 1 path.
+        #[arg(long)]
+        highlight: bool,
+
+        #[command(flatten)]
+        input: InputArg,
+    },
+}
+
+
+#[derive(Args)]
+struct InputArg {
+    /// Where to read the input from: a file, `-` for stdin, or a directory
+    /// holding `day-NN.txt` or `day-NN/src/input.txt`.
+    #[arg(long, default_value = ".")]
+    input: String,
+}
+
+
+#[derive(Clone, Copy, ValueEnum)]
+enum GraphFormat {
+    /// Graphviz DOT.
+    Dot,
+    /// A Mermaid flowchart.
+    Mermaid,
+}
+
+
+// every day we have a solution for
+const SOLUTIONS: &[&dyn Solution] = &[
+    &day_01::Day01,
+    &day_02::Day02,
+    &day_03::Day03,
+    &day_04::Day04,
+    &day_05::Day05,
+    &day_06::Day06,
+    &day_07::Day07,
+    &day_08::Day08,
+];
+
+
+// returns false if the solution failed
+fn run_part(solution: &dyn Solution, part: u8, input: &str) -> bool {
+    let start = Instant::now();
+    match solution.part(part, input) {
+        Some(Ok(answer)) => {
+            let elapsed = start.elapsed();
+            println!("Day {:02} part {}: {} ({:?})", solution.day(), part, answer, elapsed);
+            true
+        }
+        Some(Err(e)) => {
+            eprintln!("Day {:02} part {}: {}", solution.day(), part, e);
+            false
+        }
+        None => true,
+    }
+}
+
+
+fn run(day: Option<u8>, part: Option<u8>, input: &str) -> ExitCode {
+    let selected: Vec<_> = SOLUTIONS.iter()
+        .filter(|solution| day.is_none_or(|d| solution.day() == d))
+        .collect();
+    if selected.is_empty() {
+        eprintln!("No solution for day {}", day.unwrap_or_default());
+        return ExitCode::FAILURE;
+    }
+    let source = InputSource::from_arg(input);
+    if let InputSource::File(_) | InputSource::Stdin = source {
+        if selected.len() > 1 {
+            eprintln!("A single input file or stdin can only be used with --day");
+            return ExitCode::FAILURE;
+        }
+    }
+    let parts = match part {
+        Some(p) => vec![p],
+        None => vec![1, 2],
+    };
+    let start = Instant::now();
+    let mut all_succeeded = true;
+    for solution in selected {
+        let input = match source.read(solution.day()) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Day {:02}: {}", solution.day(), e);
+                return ExitCode::FAILURE;
+            }
+        };
+        for p in &parts {
+            all_succeeded &= run_part(*solution, *p, &input);
+        }
+    }
+    println!("Total: {:?}", start.elapsed());
+    if all_succeeded {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+
+// print what `describe` makes of the input for `day`
+fn show(day: u8, input: &str, describe: impl FnOnce(&str) -> Result<String, AocError>) -> ExitCode {
+    let input = match InputSource::from_arg(input).read(day) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Day {:02}: {}", day, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    match describe(&input) {
+        Ok(output) => {
+            println!("{}", output.trim_end());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Day {:02}: {}", day, e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+
+// like `show`, but failing if any map has problems
+fn validate(input: &str) -> ExitCode {
+    let mut is_valid = true;
+    let shown = show(5, input, |input| {
+        let report = day_05::parse_almanac(input)?.validate();
+        is_valid = report.is_valid();
+        Ok(report.to_string())
+    });
+    if is_valid {
+        shown
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    logging::init(logging::level_for_verbosity(cli.verbose));
+    match cli.command {
+        Command::Run { day, part, input } => run(day, part, &input),
+        Command::Validate { input } => validate(&input.input),
+        Command::Races { json: true, input } => show(6, &input.input, day_06::report::json_for),
+        Command::Races { json: false, input } => show(6, &input.input, day_06::report::tables_for),
+        Command::Explain { input } => show(7, &input.input, day_07::explain::tables_for),
+        Command::Graph { format, highlight, input } => {
+            let format = match format {
+                GraphFormat::Dot => day_08::GraphFormat::Dot,
+                GraphFormat::Mermaid => day_08::GraphFormat::Mermaid,
+            };
+            show(8, &input.input, |input| day_08::graph_for(input, format, highlight))
+        }
+    }
+}
@@ -0,0 +1,152 @@
+//! Day 1: Trebuchet?!
+//!
+//! Each line of the calibration document hides a two digit calibration value
+//! made from its first and last digits.
+
+#![warn(missing_docs)]
+
+use std::collections::HashMap;
+
+use aoc_common::parse::numbered_lines;
+use aoc_common::{AocError, Solution};
+
+
+/// Sum the calibration values made from the first and last numeric digit
+/// on each line.
+pub fn part1(input: &str) -> Result<String, AocError> {
+    let mut result = 0;
+    for (line_number, line) in numbered_lines(input) {
+        let mut first_character = 'x';
+        let mut last_character = 'x';
+        for character in line.chars() {
+            if character.is_ascii_digit() {
+                if first_character == 'x' {
+                    first_character = character;
+                }
+                last_character = character;
+            }
+        }
+        if first_character == 'x' {
+            return Err(AocError::missing(line, "a digit").at_line(line_number));
+        }
+        // both characters are digits so this can't fail
+        let number_from_line = format!("{}{}", first_character, last_character);
+        result += number_from_line.parse::<i32>().unwrap();
+    }
+    Ok(result.to_string())
+}
+
+
+/// The digit at the start of `s`, written either as a numeral or spelled out.
+pub fn get_digit_from_string_slice(s: &str) -> Option<u32> {
+    let m = HashMap::from([
+        ("1", 1),
+        ("one", 1),
+        ("2", 2),
+        ("two", 2),
+        ("3", 3),
+        ("three", 3),
+        ("4", 4),
+        ("four", 4),
+        ("5", 5),
+        ("five", 5),
+        ("6", 6),
+        ("six", 6),
+        ("7", 7),
+        ("seven", 7),
+        ("8", 8),
+        ("eight", 8),
+        ("9", 9),
+        ("nine", 9),
+        ("0", 0),
+    ]);
+
+    for k in m.keys() {
+        if s.starts_with(k) {
+            return Some(m[k])
+        }
+    }
+    None
+}
+
+
+/// Sum the calibration values where digits may also be spelled out, so
+/// `two1nine` gives 29.
+pub fn part2(input: &str) -> Result<String, AocError> {
+    let mut result = 0;
+    for (line_number, line) in numbered_lines(input) {
+        let mut first_digit = 99;
+        let mut last_digit = 0;
+        for i in 0..line.len() {
+            let substring: String = line.chars().skip(i).collect();
+            if let Some(n) = get_digit_from_string_slice(&substring) {
+                last_digit = n;
+                if first_digit == 99 {
+                    first_digit = n;
+                }
+            }
+        }
+        if first_digit == 99 {
+            return Err(AocError::missing(line, "a digit").at_line(line_number));
+        }
+        result += (first_digit * 10) + last_digit;
+    }
+    Ok(result.to_string())
+}
+
+
+/// The [`Solution`] for day 1.
+pub struct Day01;
+
+
+impl Solution for Day01 {
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AocError> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<String, AocError> {
+        part2(input)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn part1_works() {
+        let result = part1(
+"1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet"
+        );
+        assert_eq!(result, Ok("142".to_string()));
+    }
+
+    #[test]
+    fn part2_works() {
+        let result = part2(
+"two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen"
+        );
+        assert_eq!(result, Ok("281".to_string()));
+    }
+
+    #[test]
+    fn line_without_digits_is_an_error() {
+        assert_eq!(
+            part1("1abc2\nnone here"),
+            Err(AocError::Missing { line: 2, column: 10, expected: "a digit".to_string() })
+        );
+    }
+}
@@ -0,0 +1,213 @@
+//! Day 2: Cube Conundrum
+//!
+//! Each game reveals handfuls of red, green and blue cubes drawn from a bag,
+//! and we want to know which games are possible and how few cubes each needs.
+
+#![warn(missing_docs)]
+
+use std::str::FromStr;
+
+use aoc_common::parse::{parse_header, parse_lines, split_pair};
+use aoc_common::{AocError, Solution};
+
+
+/// The answers to both parts of the puzzle.
+#[derive(Debug, PartialEq, Eq)]
+pub struct DayTwoResult {
+    /// The sum of the ids of the games possible with [`BAG`].
+    pub part_one_result: u32,
+    /// The sum of the powers of the minimum cube set for each game.
+    pub part_two_result: u32,
+}
+
+
+/// The cubes the elf has in the bag for part 1.
+pub const BAG: CubeSet = CubeSet{red: 12, green: 13, blue: 14};
+
+
+/// Solve both parts of the puzzle at once.
+pub fn parts(input: &str) -> Result<DayTwoResult, AocError> {
+    let bag = BAG;
+    let mut result = DayTwoResult{part_one_result: 0, part_two_result: 0};
+    for game in parse_lines::<Game>(input)? {
+        result.part_one_result += game.game_result(&bag);
+        result.part_two_result += game.minimum_cube_set().power();
+    }
+    Ok(result)
+}
+
+
+/// Solve part 1: sum the ids of the games that are possible with [`BAG`].
+pub fn part1(input: &str) -> Result<u32, AocError> {
+    Ok(parts(input)?.part_one_result)
+}
+
+
+/// Solve part 2: sum the power of the smallest possible bag for each game.
+pub fn part2(input: &str) -> Result<u32, AocError> {
+    Ok(parts(input)?.part_two_result)
+}
+
+
+/// A number of cubes of each colour, either drawn from the bag or in it.
+#[derive(Debug)]
+pub struct CubeSet {
+    /// The number of red cubes.
+    pub red: u32,
+    /// The number of green cubes.
+    pub green: u32,
+    /// The number of blue cubes.
+    pub blue: u32,
+}
+
+
+impl CubeSet {
+    /// Whether this set of cubes could have been drawn from `other`.
+    pub fn possible_subset(&self, other: &CubeSet) -> bool {
+        other.red >= self.red &&
+        other.green >= self.green &&
+        other.blue >= self.blue
+    }
+
+    /// The red, green and blue counts multiplied together.
+    pub fn power(&self) -> u32 {
+        self.red * self.green * self.blue
+    }
+}
+
+
+impl FromStr for CubeSet {
+    type Err = AocError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut red = 0;
+        let mut green = 0;
+        let mut blue = 0;
+        let mut last_seen_int = None;
+        let parts = s.split(' ').filter(|part| !part.is_empty());
+        for part in parts {
+            if let Ok(i) = part.parse::<u32>() {
+                last_seen_int = Some(i);
+            } else {
+                let Some(count) = last_seen_int.take() else {
+                    return Err(AocError::unexpected(s, part, "a number of cubes"));
+                };
+                match part.trim_end_matches(',') {
+                    "red" => red = count,
+                    "green" => green = count,
+                    "blue" => blue = count,
+                    _ => return Err(AocError::unexpected(s, part, "red, green or blue")),
+                }
+            }
+        }
+        if last_seen_int.is_some() {
+            return Err(AocError::missing(s, "a colour"));
+        }
+        Ok(CubeSet { red, green, blue })
+    }
+}
+
+/// One line of the input: a game id and the handfuls of cubes revealed.
+#[derive(Debug)]
+pub struct Game {
+    /// The game's id.
+    pub id: u32,
+    /// Each handful of cubes revealed during the game.
+    pub selections: Vec<CubeSet>,
+}
+
+
+impl FromStr for Game {
+    type Err = AocError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (header, selections_str) = split_pair(s, ':')?;
+        let id = parse_header(header, "Game")?;
+        let mut selections: Vec<CubeSet> = vec![];
+        for selection_string in selections_str.split(';') {
+            let selection = CubeSet::from_str(selection_string)
+                .map_err(|e| e.within(s, selection_string))?;
+            selections.push(selection);
+        }
+        Ok(Game{id, selections})
+    }
+}
+
+
+impl Game {
+    /// The game's id if every handful could have come from `bag`, or 0.
+    pub fn game_result(&self, bag: &CubeSet) -> u32 {
+        let mut result = self.id;
+        for selection in self.selections.iter() {
+            if !selection.possible_subset(bag) {
+                result = 0;
+                break;
+            }
+        }
+        result
+    }
+
+    /// The fewest cubes of each colour that make every handful possible.
+    pub fn minimum_cube_set(&self) -> CubeSet {
+        let mut m = CubeSet {red: 0, green: 0, blue: 0};
+        for selection in self.selections.iter() {
+            if selection.red > m.red {
+                m.red = selection.red;
+            }
+            if selection.green > m.green {
+                m.green = selection.green;
+            }
+            if selection.blue > m.blue {
+                m.blue = selection.blue;
+            }
+        }
+        m
+    }
+}
+
+
+/// The [`Solution`] for day 2.
+pub struct Day02;
+
+
+impl Solution for Day02 {
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AocError> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, AocError> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn it_works() {
+        let result = parts(
+"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
+        );
+        assert_eq!(result, Ok(DayTwoResult{part_one_result: 8, part_two_result: 2286}));
+    }
+
+    #[test]
+    fn bad_colour_is_reported() {
+        let result = parts(
+"Game 1: 3 blue, 4 red
+Game 2: 1 blue; 2 purple");
+        assert_eq!(result, Err(AocError::Unexpected {
+            line: 2,
+            column: 19,
+            text: "purple".to_string(),
+            expected: "red, green or blue".to_string(),
+        }));
+    }
+}
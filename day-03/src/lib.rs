@@ -0,0 +1,400 @@
+//! Day 3: Gear Ratios
+//!
+//! The engine schematic is a grid of digits, symbols and `.` for empty space.
+//! Numbers next to a symbol are part numbers, and a `*` next to exactly two
+//! part numbers is a gear.
+
+#![warn(missing_docs)]
+
+use std::str::FromStr;
+
+use aoc_common::grid::{Grid, Point};
+use aoc_common::parse::numbered_lines;
+use aoc_common::{AocError, Solution};
+
+
+/// A number written across consecutive cells of one row of the schematic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberSpan {
+    /// The number the digits spell out.
+    pub value: u64,
+    /// The row the number is on.
+    pub row: i64,
+    /// The column of the first digit.
+    pub col_start: i64,
+    /// The column of the last digit.
+    pub col_end: i64,
+}
+
+
+impl NumberSpan {
+    /// Whether `point` is one of the cells the number is written in.
+    pub fn contains(&self, point: Point) -> bool {
+        point.y == self.row && (self.col_start..=self.col_end).contains(&point.x)
+    }
+
+    /// Whether `point` touches the number, including diagonally, without
+    /// being part of it.
+    pub fn is_adjacent_to(&self, point: Point) -> bool {
+        (self.row - 1..=self.row + 1).contains(&point.y)
+            && (self.col_start - 1..=self.col_end + 1).contains(&point.x)
+            && !self.contains(point)
+    }
+}
+
+
+/// Which symbols a [`SymbolRule`] applies to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Symbols {
+    /// Every symbol in the schematic.
+    Any,
+    /// Only these characters.
+    OneOf(Vec<char>),
+}
+
+
+/// How many numbers must touch a symbol for a [`SymbolRule`] to count it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Adjacency {
+    /// Exactly this many numbers.
+    Exactly(usize),
+    /// This many numbers or more.
+    AtLeast(usize),
+}
+
+
+impl Adjacency {
+    /// Whether `count` adjacent numbers satisfy this requirement.
+    pub fn allows(&self, count: usize) -> bool {
+        match self {
+            Adjacency::Exactly(n) => count == *n,
+            Adjacency::AtLeast(n) => count >= *n,
+        }
+    }
+}
+
+
+/// How a [`SymbolRule`] combines the numbers touching a symbol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregation {
+    /// Add them up.
+    Sum,
+    /// Multiply them together.
+    Product,
+    /// Take the largest.
+    Max,
+}
+
+
+impl Aggregation {
+    /// Combine `values` into one.
+    pub fn apply(&self, values: impl Iterator<Item = u64>) -> u64 {
+        match self {
+            Aggregation::Sum => values.sum(),
+            Aggregation::Product => values.product(),
+            Aggregation::Max => values.max().unwrap_or(0),
+        }
+    }
+}
+
+
+/// A question about the symbols in a schematic: which symbols to look at,
+/// how many numbers must touch each one, and how to combine those numbers.
+/// The answer is the total over all the symbols that qualify.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolRule {
+    /// The symbols to look at.
+    pub symbols: Symbols,
+    /// How many numbers must touch a symbol for it to count.
+    pub adjacency: Adjacency,
+    /// How to combine the numbers touching a symbol.
+    pub aggregation: Aggregation,
+}
+
+
+impl SymbolRule {
+    /// The rule for part 2: a `*` touching exactly two numbers is a gear,
+    /// and its ratio is their product.
+    pub fn gear() -> Self {
+        SymbolRule {
+            symbols: Symbols::OneOf(vec!['*']),
+            adjacency: Adjacency::Exactly(2),
+            aggregation: Aggregation::Product,
+        }
+    }
+}
+
+
+/// An engine schematic, indexed by `(x, y)` with `y` counting down the rows.
+///
+/// The numbers are found once when the schematic is parsed, in reading
+/// order.
+pub struct Map2D {
+    grid: Grid<char>,
+    spans: Vec<NumberSpan>,
+    empty: char,
+}
+
+
+impl FromStr for Map2D {
+    type Err = AocError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Map2D::with_empty(s, '.')
+    }
+}
+
+
+impl Map2D {
+    /// Parse a schematic that uses `empty` instead of `.` for empty space.
+    /// Numbers too big for a `u64` are an error.
+    pub fn with_empty(s: &str, empty: char) -> Result<Self, AocError> {
+        let grid: Grid<char> = s.parse()?;
+        let mut spans = Vec::new();
+        // the grid has a row for each non-blank line
+        for (y, ((line_number, line), row)) in numbered_lines(s).zip(grid.rows()).enumerate() {
+            let mut current: Option<NumberSpan> = None;
+            for (x, c) in row.iter().enumerate() {
+                match (c.to_digit(10), current.as_mut()) {
+                    (Some(digit), Some(span)) => {
+                        span.value = span.value
+                                         .checked_mul(10)
+                                         .and_then(|value| value.checked_add(digit as u64))
+                                         .ok_or_else(|| number_too_big(line, span.col_start).at_line(line_number))?;
+                        span.col_end = x as i64;
+                    }
+                    (Some(digit), None) => {
+                        let (x, y) = (x as i64, y as i64);
+                        current = Some(NumberSpan { value: digit as u64, row: y, col_start: x, col_end: x });
+                    }
+                    (None, _) => spans.extend(current.take()),
+                }
+            }
+            // a number can run up to the end of the row
+            spans.extend(current);
+        }
+        Ok(Map2D { grid, spans, empty })
+    }
+
+    /// The character at `point`, or the empty character if that is outside
+    /// the map.
+    pub fn get_coordinate(&self, point: Point) -> char {
+        self.grid.get(point).copied().unwrap_or(self.empty)
+    }
+
+    /// Whether `point` holds something other than a digit or empty space.
+    pub fn is_symbol(&self, point: Point) -> bool {
+        let c = self.get_coordinate(point);
+        c != self.empty && !c.is_ascii_digit()
+    }
+
+    /// Every number in the schematic, in reading order.
+    pub fn number_spans(&self) -> &[NumberSpan] {
+        &self.spans
+    }
+
+    /// The numbers on rows `first..=last`.
+    fn spans_in_rows(&self, first: i64, last: i64) -> &[NumberSpan] {
+        let start = self.spans.partition_point(|span| span.row < first);
+        let end = self.spans.partition_point(|span| span.row <= last);
+        &self.spans[start..end]
+    }
+
+    /// The number with a digit at `point`, if there is one.
+    pub fn span_at(&self, point: Point) -> Option<&NumberSpan> {
+        self.spans_in_rows(point.y, point.y).iter().find(|span| span.contains(point))
+    }
+
+    /// The numbers touching `point`, including diagonally.
+    pub fn spans_adjacent_to(&self, point: Point) -> impl Iterator<Item = &NumberSpan> {
+        self.spans_in_rows(point.y - 1, point.y + 1)
+            .iter()
+            .filter(move |span| span.is_adjacent_to(point))
+    }
+
+    /// The symbols touching `span`, including diagonally, with their
+    /// positions.
+    pub fn symbols_adjacent_to<'a>(&'a self, span: &NumberSpan) -> impl Iterator<Item = (Point, char)> + 'a {
+        let top_left = Point::new(span.col_start - 1, span.row - 1);
+        let bottom_right = Point::new(span.col_end + 1, span.row + 1);
+        self.grid.region(top_left, bottom_right)
+                 .filter(|(point, _)| self.is_symbol(*point))
+                 .map(|(point, c)| (point, *c))
+    }
+
+    /// The numbers touching at least one symbol.
+    pub fn part_numbers(&self) -> impl Iterator<Item = &NumberSpan> {
+        self.spans.iter().filter(|span| self.symbols_adjacent_to(span).next().is_some())
+    }
+
+    /// The sum of all the part numbers in the schematic.
+    pub fn get_part_number_total(&self) -> u64 {
+        self.part_numbers().map(|span| span.value).sum()
+    }
+
+    /// The value `rule` gives the symbol at `point`, or `None` if there is
+    /// no symbol there that the rule counts.
+    pub fn symbol_value(&self, point: Point, rule: &SymbolRule) -> Option<u64> {
+        if !self.is_symbol(point) {
+            return None;
+        }
+        if let Symbols::OneOf(symbols) = &rule.symbols {
+            if !symbols.contains(&self.get_coordinate(point)) {
+                return None;
+            }
+        }
+        let adjacent_numbers: Vec<u64> = self.spans_adjacent_to(point).map(|span| span.value).collect();
+        log::trace!("`{}` at ({}, {}) touches {:?}", self.get_coordinate(point), point.x, point.y, adjacent_numbers);
+        if !rule.adjacency.allows(adjacent_numbers.len()) {
+            return None;
+        }
+        Some(rule.aggregation.apply(adjacent_numbers.into_iter()))
+    }
+
+    /// The total of the values `rule` gives every symbol it counts.
+    pub fn rule_total(&self, rule: &SymbolRule) -> u64 {
+        self.grid.points()
+                 .filter_map(|point| self.symbol_value(point, rule))
+                 .sum()
+    }
+
+    /// The gear ratio of the `*` at `point`, or 0 if it isn't a gear.
+    pub fn gear_ratio_at_location(&self, point: Point) -> u64 {
+        self.symbol_value(point, &SymbolRule::gear()).unwrap_or(0)
+    }
+
+    /// The sum of all the gear ratios in the schematic.
+    pub fn get_gear_ratio_total(&self) -> u64 {
+        self.rule_total(&SymbolRule::gear())
+    }
+}
+
+
+// the whole run of digits starting at column `col_start` of `line`, which
+// spells a number too big for a u64
+fn number_too_big(line: &str, col_start: i64) -> AocError {
+    let (start, _) = line.char_indices().nth(col_start as usize).expect("the number is on the line");
+    let digits = &line[start..];
+    let end = digits.find(|c: char| !c.is_ascii_digit()).unwrap_or(digits.len());
+    AocError::invalid_number(line, &digits[..end])
+}
+
+
+/// Solve part 1: sum the part numbers in the schematic.
+pub fn part1(input: &str) -> Result<u64, AocError> {
+    Ok(Map2D::from_str(input)?.get_part_number_total())
+}
+
+
+/// Solve part 2: sum the gear ratios in the schematic.
+pub fn part2(input: &str) -> Result<u64, AocError> {
+    Ok(Map2D::from_str(input)?.get_gear_ratio_total())
+}
+
+
+/// The [`Solution`] for day 3.
+pub struct Day03;
+
+
+impl Solution for Day03 {
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AocError> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, AocError> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn it_works() {
+        let input =
+"467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+        let map = Map2D::from_str(input).expect("invalid input");
+        assert_eq!(map.get_part_number_total(), 4361);
+        assert_eq!(map.get_gear_ratio_total(), 467835);
+
+        let input2 =
+"12.......*..
++.........34
+.......-12..
+..78........
+..*....60...
+78.........9
+.5.....23..$
+8...90*12...
+............
+2.2......12.
+.*.........*
+1.1..503+.56";
+        let map2 = Map2D::from_str(input2).expect("invalid input");
+        assert_eq!(map2.get_gear_ratio_total(), 6756);
+    }
+
+    #[test]
+    fn wide_numbers_are_whole_spans() {
+        let map = Map2D::from_str("12345*...\n.....6789").expect("invalid input");
+        assert_eq!(map.number_spans()[0], NumberSpan { value: 12345, row: 0, col_start: 0, col_end: 4 });
+        assert_eq!(map.span_at(Point::new(7, 1)).map(|span| span.value), Some(6789));
+        let gear = Point::new(5, 0);
+        assert_eq!(map.spans_adjacent_to(gear).count(), 2);
+        assert_eq!(map.symbols_adjacent_to(&map.number_spans()[1]).collect::<Vec<_>>(), vec![(gear, '*')]);
+        assert_eq!(map.get_part_number_total(), 12345 + 6789);
+        assert_eq!(map.get_gear_ratio_total(), 12345 * 6789);
+    }
+
+    #[test]
+    fn symbol_rules() {
+        let input =
+"467__114__
+___*______
+__35__633_
+______#___
+617*______";
+        let map = Map2D::with_empty(input, '_').expect("invalid input");
+        assert_eq!(map.get_gear_ratio_total(), 467 * 35);
+        let any_symbol = |adjacency, aggregation| SymbolRule { symbols: Symbols::Any, adjacency, aggregation };
+        assert_eq!(map.rule_total(&any_symbol(Adjacency::AtLeast(1), Aggregation::Sum)), 467 + 35 + 633 + 617);
+        assert_eq!(map.rule_total(&any_symbol(Adjacency::Exactly(1), Aggregation::Max)), 633 + 617);
+        let hashes = SymbolRule { symbols: Symbols::OneOf(vec!['#']), ..SymbolRule::gear() };
+        assert_eq!(map.rule_total(&hashes), 0);
+    }
+
+    #[test]
+    fn numbers_too_big_are_an_error() {
+        let input = format!("{}\n\n.123456789012345678901234*", ".".repeat(26));
+        assert_eq!(part1(&input), Err(AocError::InvalidNumber {
+            line: 3,
+            column: 2,
+            text: "123456789012345678901234".to_string(),
+        }));
+        assert_eq!(part1("18446744073709551615*"), Ok(u64::MAX));
+    }
+
+    #[test]
+    fn ragged_rows_are_an_error() {
+        let result = Map2D::from_str("467..\n...*.\n..35..6");
+        assert_eq!(result.err(), Some(AocError::Unexpected {
+            line: 3,
+            column: 6,
+            text: ".6".to_string(),
+            expected: "end of line".to_string(),
+        }));
+    }
+}
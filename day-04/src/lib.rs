@@ -0,0 +1,184 @@
+//! Day 4: Scratchcards
+//!
+//! Each scratchcard lists its winning numbers and the numbers you have. In
+//! part 2 matching numbers win copies of the cards that follow.
+
+#![warn(missing_docs)]
+
+use std::str::FromStr;
+
+use aoc_common::parse::{numbered_lines, parse_header, parse_number_list, split_pair};
+use aoc_common::{AocError, Solution};
+
+
+/// One scratchcard.
+#[derive(Debug)]
+pub struct GameCard {
+    /// The card number.
+    pub game_id: usize,
+    /// The numbers before the `|`.
+    pub winning_numbers: Vec<u32>,
+    /// The numbers after the `|`.
+    pub your_numbers: Vec<u32>,
+}
+
+
+impl FromStr for GameCard {
+    type Err = AocError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (header_section, number_lists_section) = split_pair(s, ':')?;
+        let game_id = parse_header(header_section, "Card")?;
+        let (winning_numbers_str, your_numbers_str) = split_pair(number_lists_section, '|')
+            .map_err(|e| e.within(s, number_lists_section))?;
+        let winning_numbers = parse_number_list(winning_numbers_str)
+            .map_err(|e| e.within(s, winning_numbers_str))?;
+        let your_numbers = parse_number_list(your_numbers_str)
+            .map_err(|e| e.within(s, your_numbers_str))?;
+        Ok(GameCard {
+            game_id,
+            winning_numbers,
+            your_numbers
+        })
+    }
+}
+
+
+/// Parse every card in the input. The cards must be numbered from 1 in
+/// order, as part 2 finds the cards it copies by their position.
+pub fn parse_cards(input: &str) -> Result<Vec<GameCard>, AocError> {
+    numbered_lines(input)
+        .enumerate()
+        .map(|(card_index, (line_number, line))| {
+            let card: GameCard = line.parse().map_err(|e: AocError| e.at_line(line_number))?;
+            if card.game_id != card_index + 1 {
+                // the header parsed, so its second field is the card number
+                let id_text = line.split(':').next().and_then(|header| header.split_whitespace().nth(1)).unwrap_or(line);
+                return Err(AocError::unexpected(line, id_text, format!("card {}", card_index + 1)).at_line(line_number));
+            }
+            Ok(card)
+        })
+        .collect()
+}
+
+
+impl GameCard {
+    /// The points the card is worth and how many of your numbers match. The
+    /// points stop doubling at `u32::MAX`.
+    pub fn score(&self) -> (u32, usize) {
+        let mut points = 0u32;
+        let mut next_score = 1u32;
+        let mut matching_numbers = 0;
+        for my_number in &self.your_numbers {
+            for winning_number in &self.winning_numbers {
+                if my_number == winning_number {
+                    points = next_score;
+                    next_score = next_score.saturating_mul(2);
+                    matching_numbers += 1;
+                }
+            }
+        }
+        (points, matching_numbers)
+    }
+
+    /// The total number of copies this card wins, including the copies won
+    /// by those copies. `game_card_table` is indexed by card number, and
+    /// cards past the end of it can't be won.
+    pub fn num_cards_won(&self, game_card_table: &Vec<GameCard>) -> usize {
+        let mut cards_won = 0;
+        let matching_numbers = self.score().1;
+        // recursively add the cards won by the copied cards
+        for copied_card_id in self.game_id + 1..self.game_id + 1 + matching_numbers {
+            if let Some(copied_card) = game_card_table.get(copied_card_id) {
+                cards_won += 1 + copied_card.num_cards_won(game_card_table);
+            }
+        }
+        cards_won
+    }
+}
+
+
+/// Solve part 1: the total points of all the cards.
+pub fn part1(input: &str) -> Result<u32, AocError> {
+    let mut points = 0u32;
+    for game in parse_cards(input)? {
+        points = points.checked_add(game.score().0)
+                       .ok_or_else(|| AocError::unsolvable("the total points don't fit in a u32"))?;
+    }
+    Ok(points)
+}
+
+
+/// Solve part 2: the total number of cards you end up with.
+pub fn part2(input: &str) -> Result<usize, AocError> {
+    let mut game_card_table = vec![GameCard {game_id:0, winning_numbers: vec![], your_numbers: vec![]}];
+    game_card_table.extend(parse_cards(input)?);
+    let mut num_cards = 0;
+    for game_card in &game_card_table {
+        num_cards += game_card.num_cards_won(&game_card_table);
+    }
+    Ok(num_cards + game_card_table.len() - 1)
+}
+
+
+/// The [`Solution`] for day 4.
+pub struct Day04;
+
+
+impl Solution for Day04 {
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AocError> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, AocError> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn it_works() {
+        let input =
+"Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+        assert_eq!(part1(input), Ok(13));
+        assert_eq!(part2(input), Ok(30));
+    }
+
+    #[test]
+    fn bad_number_is_reported() {
+        let result = part1("Card 1: 41 48 | 83 86\nCard 2: 13 3x | 61 30");
+        assert_eq!(result, Err(AocError::InvalidNumber { line: 2, column: 12, text: "3x".to_string() }));
+    }
+
+    #[test]
+    fn cards_must_be_in_order() {
+        let result = part2("Card 1: 1 | 1\nCard 1: 1 | 1");
+        assert_eq!(result, Err(AocError::Unexpected {
+            line: 2,
+            column: 6,
+            text: "1".to_string(),
+            expected: "card 2".to_string(),
+        }));
+        assert!(part1("Card 1: 1 | 1\n\nCard  3: 2 | 2").is_err());
+    }
+
+    #[test]
+    fn many_matches_do_not_overflow() {
+        let numbers: Vec<String> = (1..=40).map(|n| n.to_string()).collect();
+        let card = format!("Card 1: {0} | {0}", numbers.join(" "));
+        assert_eq!(part1(&card), Ok(u32::MAX));
+        assert_eq!(part2(&card), Ok(1));
+        assert!(part1(&format!("{}\nCard 2: 1 | 1", card)).is_err());
+    }
+}
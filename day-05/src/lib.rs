@@ -0,0 +1,454 @@
+//! Day 5: If You Give A Seed A Fertilizer
+//!
+//! The almanac lists seeds and a series of maps which convert each seed to a
+//! soil, the soil to a fertilizer and so on until we reach a location.
+
+#![warn(missing_docs)]
+
+pub mod piecewise;
+pub mod validate;
+
+use std::ops::Range;
+use std::str::FromStr;
+
+use aoc_common::parse::{blocks, parse_labelled_numbers, parse_number_array, Block};
+use aoc_common::{AocError, Solution};
+
+pub use piecewise::PiecewiseMap;
+pub use validate::{validate, ValidationReport};
+
+
+/// One line of a map: `range_len` values starting at `src_range_start` map
+/// to the values starting at `dest_range_start`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GardeningMapEntry {
+    /// The first value of the destination range.
+    pub dest_range_start: u64,
+    /// The first value of the source range.
+    pub src_range_start: u64,
+    /// The number of values in each range.
+    pub range_len: u64,
+}
+
+
+/// One of the maps in the almanac, such as `seed-to-soil`. Values not
+/// covered by any entry map to themselves.
+pub type GardeningMap = Vec<GardeningMapEntry>;
+
+
+impl FromStr for GardeningMapEntry {
+    type Err = AocError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [dest_range_start, src_range_start, range_len] = parse_number_array(s)?;
+        Ok(GardeningMapEntry {
+            dest_range_start,
+            src_range_start,
+            range_len,
+        })
+    }
+}
+
+
+impl GardeningMapEntry {
+    /// The values this entry maps.
+    pub fn src_range(&self) -> Range<u64> {
+        self.src_range_start..self.src_range_start.saturating_add(self.range_len)
+    }
+
+    /// Whether the source or destination range runs past `u64::MAX`.
+    /// Everything that maps values ignores such entries, and
+    /// [`validate`](crate::validate()) reports them.
+    pub fn overflows(&self) -> bool {
+        self.src_range_start.max(self.dest_range_start).checked_add(self.range_len).is_none()
+    }
+}
+
+
+/// Map a value through one map.
+pub fn get_mapped_value(map: &GardeningMap, value: u64) -> u64 {
+    for entry in map.iter().filter(|entry| !entry.overflows()) {
+        if entry.src_range().contains(&value) {
+            let diff = value - entry.src_range_start;
+            return entry.dest_range_start + diff
+        }
+    }
+    value
+}
+
+
+/// Map a seed through each of the maps in turn to find its location.
+pub fn traverse_maps(maps: &[GardeningMap], seed: u64) -> u64 {
+    let mut mapped_value = seed;
+    for map in maps {
+        mapped_value = get_mapped_value(map, mapped_value);
+    }
+    mapped_value
+}
+
+
+/// Map every value in `range` through one map. The result is a set of
+/// ranges, since different parts of `range` may be covered by different
+/// entries, so the work done depends on the number of entries rather than
+/// the number of values. None of the ranges are empty.
+pub fn map_range(map: &GardeningMap, range: Range<u64>) -> Vec<Range<u64>> {
+    let mut unmapped: Vec<Range<u64>> = [range].into_iter().filter(|r| !r.is_empty()).collect();
+    let mut mapped = vec![];
+    for entry in map.iter().filter(|entry| !entry.overflows()) {
+        let src = entry.src_range();
+        let mut remaining = vec![];
+        for range in unmapped {
+            let overlap = range.start.max(src.start)..range.end.min(src.end);
+            if overlap.is_empty() {
+                remaining.push(range);
+                continue;
+            }
+            let dest_start = entry.dest_range_start + (overlap.start - src.start);
+            mapped.push(dest_start..dest_start + (overlap.end - overlap.start));
+            // the parts either side of the entry may be covered by another one
+            let before = range.start..overlap.start;
+            let after = overlap.end..range.end;
+            remaining.extend([before, after].into_iter().filter(|r| !r.is_empty()));
+        }
+        unmapped = remaining;
+    }
+    // values not covered by any entry map to themselves
+    mapped.extend(unmapped);
+    mapped
+}
+
+
+/// Map ranges of seeds through each of the maps in turn to find the ranges
+/// of locations they end up at.
+pub fn traverse_maps_ranges(maps: &[GardeningMap], seed_ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
+    maps.iter().fold(seed_ranges, |ranges, map| {
+        ranges.into_iter()
+              .flat_map(|range| map_range(map, range))
+              .collect()
+    })
+}
+
+
+/// One map in the almanac, such as `seed-to-soil`, with the names of the
+/// categories it converts between.
+#[derive(Debug)]
+pub struct Stage {
+    /// The category the map converts from, such as `seed`.
+    pub from: String,
+    /// The category the map converts to, such as `soil`.
+    pub to: String,
+    /// The map itself.
+    pub map: GardeningMap,
+}
+
+
+/// The categories named in a map header such as `seed-to-soil map:`.
+pub fn parse_stage_header(header: &str) -> Result<(&str, &str), AocError> {
+    let not_a_header = || AocError::unexpected(header, header, "a map header such as `seed-to-soil map:`");
+    let names = header.strip_suffix(" map:").ok_or_else(not_a_header)?;
+    match names.split_once("-to-") {
+        Some((from, to)) if !from.is_empty() && !to.is_empty() => Ok((from, to)),
+        _ => Err(not_a_header()),
+    }
+}
+
+
+/// Parse one map: a header such as `seed-to-soil map:` followed by a line
+/// for each entry.
+pub fn parse_stage(block: &Block) -> Result<Stage, AocError> {
+    let (header_line_number, header) = block.first();
+    let (from, to) = parse_stage_header(header).map_err(|e| e.at_line(header_line_number))?;
+    let map = block.rest()
+                   .iter()
+                   .map(|(line_number, line)| GardeningMapEntry::from_str(line).map_err(|e| e.at_line(*line_number)))
+                   .collect::<Result<GardeningMap, AocError>>()?;
+    Ok(Stage { from: from.to_string(), to: to.to_string(), map })
+}
+
+
+/// Turn pairs of (start, length) into ranges of seeds, leaving out any of
+/// length 0.
+pub fn seed_ranges(unexpanded_seeds: &[u64]) -> Vec<Range<u64>> {
+    unexpanded_seeds.chunks_exact(2)
+                    .map(|pair| pair[0]..pair[0].saturating_add(pair[1]))
+                    .filter(|range| !range.is_empty())
+                    .collect()
+}
+
+
+/// The seeds and the chain of maps that take them from one category to the
+/// next, starting at `seed`.
+#[derive(Debug)]
+pub struct Almanac {
+    /// The numbers on the `seeds:` line.
+    pub seeds: Vec<u64>,
+    /// The maps, each converting from the category the one before converts
+    /// to.
+    pub stages: Vec<Stage>,
+}
+
+
+impl Almanac {
+    /// Every category in the order the maps visit them, starting with
+    /// `seed`.
+    pub fn categories(&self) -> impl Iterator<Item = &str> {
+        std::iter::once("seed").chain(self.stages.iter().map(|stage| stage.to.as_str()))
+    }
+
+    /// The maps that convert `from` to `to`, or `None` if `to` doesn't come
+    /// after `from` in the chain.
+    pub fn stages_between(&self, from: &str, to: &str) -> Option<&[Stage]> {
+        let start = self.stages.iter().position(|stage| stage.from == from)?;
+        let end = self.stages.iter().position(|stage| stage.to == to)?;
+        (start <= end).then(|| &self.stages[start..=end])
+    }
+
+    /// The single map that converts `from` to `to`.
+    pub fn map_between(&self, from: &str, to: &str) -> Option<PiecewiseMap> {
+        let stages = self.stages_between(from, to)?;
+        Some(PiecewiseMap::compose(stages.iter().map(|stage| &stage.map)))
+    }
+
+    /// The `to` that `value` of category `from` needs, such as the
+    /// fertilizer for a seed.
+    pub fn lookup(&self, from: &str, to: &str, value: u64) -> Option<u64> {
+        Some(self.map_between(from, to)?.get(value))
+    }
+
+    /// Every `from` that needs `value` of category `to`, such as the seeds
+    /// that end up at a location.
+    pub fn reverse_lookup(&self, from: &str, to: &str, value: u64) -> Option<Vec<u64>> {
+        Some(self.map_between(from, to)?.preimage(value))
+    }
+
+    /// Check each map for overlapping entries, overflow, and values that are
+    /// reached twice or not at all.
+    pub fn validate(&self) -> ValidationReport {
+        validate(self)
+    }
+
+    /// The maps in order, without their names.
+    pub fn maps(&self) -> impl Iterator<Item = &GardeningMap> {
+        self.stages.iter().map(|stage| &stage.map)
+    }
+}
+
+
+/// Parse the almanac, checking that each map converts from the category the
+/// one before it converts to.
+pub fn parse_almanac(input: &str) -> Result<Almanac, AocError> {
+    let blocks = blocks(input);
+    let Some((seeds_block, map_blocks)) = blocks.split_first() else {
+        return Err(AocError::missing("", "`seeds:`").at_line(1));
+    };
+    let (seeds_line_number, seeds_line) = seeds_block.first();
+    let seeds = parse_labelled_numbers(seeds_line, "seeds:")
+        .map_err(|e| e.at_line(seeds_line_number))?;
+    if let Some((line_number, line)) = seeds_block.rest().first() {
+        return Err(AocError::unexpected(line, line, "a blank line").at_line(*line_number));
+    }
+    let mut stages: Vec<Stage> = vec![];
+    for block in map_blocks {
+        let stage = parse_stage(block)?;
+        let (line_number, header) = block.first();
+        let previous = stages.last().map_or("seed", |previous| previous.to.as_str());
+        if stage.from != previous {
+            let from = &header[..stage.from.len()];
+            return Err(AocError::unexpected(header, from, format!("`{}`", previous)).at_line(line_number));
+        }
+        if stage.to == "seed" || stages.iter().any(|earlier| earlier.to == stage.to) {
+            let to = &header[stage.from.len() + "-to-".len()..][..stage.to.len()];
+            return Err(AocError::unexpected(header, to, "a category not already mapped to").at_line(line_number));
+        }
+        stages.push(stage);
+    }
+    Ok(Almanac { seeds, stages })
+}
+
+
+/// Solve part 1: the lowest location of any of the listed seeds.
+pub fn part1(input: &str) -> Result<u64, AocError> {
+    let almanac = parse_almanac(input)?;
+    let seed_to_location = PiecewiseMap::compose(almanac.maps());
+    log::debug!("seed-to-location map has {} segments", seed_to_location.segments().len());
+    almanac.seeds
+           .iter()
+           .map(|s| {
+               let location = seed_to_location.get(*s);
+               log::trace!("seed {} is at location {}", s, location);
+               location
+           })
+           .min()
+           .ok_or_else(|| AocError::unsolvable("there are no seeds"))
+}
+
+
+/// Solve part 2: the lowest location of any seed in the listed ranges.
+pub fn part2(input: &str) -> Result<u64, AocError> {
+    let almanac = parse_almanac(input)?;
+    if almanac.seeds.len() % 2 != 0 {
+        // the almanac parsed, so its first block is the seeds
+        let (line_number, seeds_line) = blocks(input)[0].first();
+        return Err(AocError::missing(seeds_line, "a length for the last range of seeds").at_line(line_number));
+    }
+    let seed_to_location = PiecewiseMap::compose(almanac.maps());
+    seed_ranges(&almanac.seeds).into_iter()
+                               .flat_map(|range| {
+                                   let locations = seed_to_location.map_range(range.clone());
+                                   log::trace!("seeds {:?} are at locations {:?}", range, locations);
+                                   locations
+                               })
+                               .map(|range| range.start)
+                               .min()
+                               .ok_or_else(|| AocError::unsolvable("there are no seeds"))
+}
+
+
+/// Solve both parts of the puzzle.
+pub fn both_parts(input: &str) -> Result<(u64, u64), AocError> {
+    Ok((part1(input)?, part2(input)?))
+}
+
+
+/// The [`Solution`] for day 5.
+pub struct Day05;
+
+
+impl Solution for Day05 {
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AocError> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, AocError> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str =
+"seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+    #[test]
+    fn it_works() {
+        assert_eq!(both_parts(EXAMPLE), Ok((35, 46)));
+        //assert_eq!(part2(input), 30);
+    }
+
+    #[test]
+    fn lookups_between_named_categories() {
+        let almanac = parse_almanac(EXAMPLE).expect("invalid input");
+        assert_eq!(almanac.categories().last(), Some("location"));
+        assert_eq!(almanac.lookup("seed", "fertilizer", 79), Some(81));
+        assert_eq!(almanac.lookup("soil", "light", 81), Some(74));
+        assert_eq!(almanac.lookup("seed", "location", 79), Some(82));
+        assert_eq!(almanac.reverse_lookup("seed", "location", 82), Some(vec![79]));
+        assert_eq!(almanac.lookup("location", "seed", 82), None);
+        assert_eq!(almanac.lookup("seed", "sunlight", 79), None);
+    }
+
+    #[test]
+    fn headers_must_chain() {
+        let input = "seeds: 79\n\nseed-to-soil map:\n50 98 2\n\nwater-to-light map:\n88 18 7";
+        assert_eq!(part1(input), Err(AocError::Unexpected {
+            line: 6,
+            column: 1,
+            text: "water".to_string(),
+            expected: "`soil`".to_string(),
+        }));
+    }
+
+    #[test]
+    fn overflowing_entries_are_ignored() {
+        let input = "seeds: 1 2\n\nseed-to-soil map:\n18446744073709551610 0 10";
+        assert_eq!(both_parts(input), Ok((1, 1)));
+        let almanac = parse_almanac(input).expect("invalid input");
+        assert!(almanac.stages[0].map[0].overflows());
+        assert_eq!(get_mapped_value(&almanac.stages[0].map, 2), 2);
+        assert_eq!(PiecewiseMap::from_map(&almanac.stages[0].map), PiecewiseMap::identity());
+    }
+
+    #[test]
+    fn ranges_are_split_on_entry_boundaries() {
+        let map = vec![
+            GardeningMapEntry { dest_range_start: 50, src_range_start: 98, range_len: 2 },
+            GardeningMapEntry { dest_range_start: 52, src_range_start: 50, range_len: 48 },
+        ];
+        let mut mapped = map_range(&map, 40..100);
+        mapped.sort_by_key(|range| range.start);
+        assert_eq!(mapped, vec![40..50, 50..52, 52..100]);
+        assert_eq!(map_range(&map, 97..99), vec![50..51, 99..100]);
+        assert_eq!(map_range(&map, 0..10), vec![0..10]);
+        assert_eq!(map_range(&map, 60..60), vec![]);
+    }
+
+    #[test]
+    fn empty_seed_ranges_are_ignored() {
+        let input = EXAMPLE.replace("seeds: 79 14 55 13", "seeds: 79 14 55 13 1 0");
+        assert_eq!(seed_ranges(&[79, 14, 1, 0]), vec![79..93]);
+        assert_eq!(part2(&input), Ok(46));
+    }
+
+    #[test]
+    fn odd_seed_count_is_reported_on_the_seeds_line() {
+        let input = format!("\n\n{}", EXAMPLE.replace("seeds: 79 14 55 13", "seeds: 79 14 55"));
+        assert_eq!(part2(&input), Err(AocError::Missing {
+            line: 3,
+            column: 16,
+            expected: "a length for the last range of seeds".to_string(),
+        }));
+    }
+
+    #[test]
+    fn short_map_entry_is_reported() {
+        let input =
+"seeds: 79 14
+
+seed-to-soil map:
+50 98 2
+52 50";
+        assert_eq!(part1(input), Err(AocError::Missing {
+            line: 5,
+            column: 6,
+            expected: "3 numbers".to_string(),
+        }));
+    }
+}
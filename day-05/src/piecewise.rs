@@ -0,0 +1,239 @@
+//! A map built once from one or more almanac maps, for fast lookups.
+//!
+//! Every map in the almanac is piecewise linear: each entry shifts a range of
+//! values by a fixed amount and everything else stays put. Following one
+//! such map with another gives another piecewise linear map, so the whole
+//! chain from seed to location can be flattened into a single sorted list of
+//! segments.
+
+use std::fmt;
+use std::ops::Range;
+
+use crate::{GardeningMap, GardeningMapEntry};
+
+
+/// A range of values that are all shifted by the same amount.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    /// The values the segment maps.
+    pub src: Range<u64>,
+    /// The value `src.start` maps to.
+    pub dest_start: u64,
+}
+
+
+impl Segment {
+    /// The value `value`, which must be in `src`, maps to.
+    pub fn map(&self, value: u64) -> u64 {
+        self.dest_start + (value - self.src.start)
+    }
+
+    /// The values the segment maps to.
+    pub fn dest(&self) -> Range<u64> {
+        self.dest_start..self.dest_start + (self.src.end - self.src.start)
+    }
+
+    /// Whether the segment maps every value to itself.
+    pub fn is_identity(&self) -> bool {
+        self.dest_start == self.src.start
+    }
+}
+
+
+/// A piecewise linear map over `0..u64::MAX`, stored as sorted segments
+/// that cover the whole domain without overlapping. `u64::MAX` itself maps
+/// to itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PiecewiseMap {
+    segments: Vec<Segment>,
+}
+
+
+impl PiecewiseMap {
+    /// The map that sends every value to itself.
+    pub fn identity() -> Self {
+        PiecewiseMap { segments: vec![Segment { src: 0..u64::MAX, dest_start: 0 }] }
+    }
+
+    /// The map equivalent to one almanac map. Where entries overlap the
+    /// first one wins, as it does in [`get_mapped_value`](crate::get_mapped_value).
+    /// Entries that run past `u64::MAX` are ignored.
+    pub fn from_map(map: &GardeningMap) -> Self {
+        let map: Vec<&GardeningMapEntry> = map.iter().filter(|entry| !entry.overflows()).collect();
+        let mut boundaries: Vec<u64> = map.iter()
+                                          .flat_map(|entry| [entry.src_range().start, entry.src_range().end])
+                                          .chain([0, u64::MAX])
+                                          .collect();
+        boundaries.sort_unstable();
+        boundaries.dedup();
+        let segments = boundaries.windows(2).map(|pair| {
+            let src = pair[0]..pair[1];
+            let dest_start = map.iter()
+                                .find(|entry| entry.src_range().contains(&src.start))
+                                .map_or(src.start, |entry| entry.dest_range_start + (src.start - entry.src_range_start));
+            Segment { src, dest_start }
+        });
+        PiecewiseMap { segments: merged(segments) }
+    }
+
+    /// The map that applies `self` and then `next`.
+    pub fn then(&self, next: &PiecewiseMap) -> Self {
+        let segments = self.segments.iter().flat_map(|segment| {
+            let dest = segment.dest();
+            next.overlapping(&dest).iter().map(move |next_segment| {
+                let overlap_start = dest.start.max(next_segment.src.start);
+                let overlap_end = dest.end.min(next_segment.src.end);
+                let src_start = segment.src.start + (overlap_start - dest.start);
+                Segment {
+                    src: src_start..src_start + (overlap_end - overlap_start),
+                    dest_start: next_segment.map(overlap_start),
+                }
+            })
+        });
+        PiecewiseMap { segments: merged(segments) }
+    }
+
+    /// The map that applies each of `maps` in turn.
+    pub fn compose<'a>(maps: impl IntoIterator<Item = &'a GardeningMap>) -> Self {
+        maps.into_iter().fold(PiecewiseMap::identity(), |composed, map| composed.then(&PiecewiseMap::from_map(map)))
+    }
+
+    /// The segments, in order.
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// The segments that map some of the values in `range`.
+    fn overlapping(&self, range: &Range<u64>) -> &[Segment] {
+        let start = self.segments.partition_point(|segment| segment.src.end <= range.start);
+        let end = self.segments.partition_point(|segment| segment.src.start < range.end);
+        &self.segments[start..end.max(start)]
+    }
+
+    /// The value `value` maps to.
+    pub fn get(&self, value: u64) -> u64 {
+        let i = self.segments.partition_point(|segment| segment.src.end <= value);
+        self.segments.get(i).map_or(value, |segment| segment.map(value))
+    }
+
+    /// The ranges the values in `range` map to. None of them are empty.
+    pub fn map_range(&self, range: Range<u64>) -> Vec<Range<u64>> {
+        if range.is_empty() {
+            return vec![];
+        }
+        self.overlapping(&range)
+            .iter()
+            .map(|segment| {
+                let start = range.start.max(segment.src.start);
+                let end = range.end.min(segment.src.end);
+                segment.map(start)..segment.map(start) + (end - start)
+            })
+            .collect()
+    }
+
+    /// Every value that maps to `value`, in increasing order.
+    pub fn preimage(&self, value: u64) -> Vec<u64> {
+        self.segments.iter()
+                     .filter(|segment| segment.dest().contains(&value))
+                     .map(|segment| segment.src.start + (value - segment.dest_start))
+                     .collect()
+    }
+
+    /// The map that undoes this one, or `None` if two values map to the
+    /// same place or some value has nothing mapping to it.
+    pub fn inverse(&self) -> Option<Self> {
+        let mut segments: Vec<Segment> = self.segments.iter()
+                                                      .map(|segment| Segment { src: segment.dest(), dest_start: segment.src.start })
+                                                      .collect();
+        segments.sort_unstable_by_key(|segment| segment.src.start);
+        let mut expected_start = 0;
+        for segment in &segments {
+            if segment.src.start != expected_start {
+                return None;
+            }
+            expected_start = segment.src.end;
+        }
+        (expected_start == u64::MAX).then(|| PiecewiseMap { segments: merged(segments) })
+    }
+
+    /// The map as almanac entries, leaving out the values that map to
+    /// themselves.
+    pub fn to_entries(&self) -> GardeningMap {
+        self.segments.iter()
+                     .filter(|segment| !segment.is_identity())
+                     .map(|segment| GardeningMapEntry {
+                         dest_range_start: segment.dest_start,
+                         src_range_start: segment.src.start,
+                         range_len: segment.src.end - segment.src.start,
+                     })
+                     .collect()
+    }
+}
+
+
+/// Join up neighbouring segments that shift by the same amount.
+fn merged(segments: impl IntoIterator<Item = Segment>) -> Vec<Segment> {
+    let mut result: Vec<Segment> = vec![];
+    for segment in segments {
+        if segment.src.is_empty() {
+            continue;
+        }
+        if let Some(last) = result.last_mut() {
+            if last.src.end == segment.src.start && last.dest().end == segment.dest_start {
+                last.src.end = segment.src.end;
+                continue;
+            }
+        }
+        result.push(segment);
+    }
+    result
+}
+
+
+/// Writes the map in the almanac's format, one entry to a line.
+impl fmt::Display for PiecewiseMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in self.to_entries() {
+            writeln!(f, "{} {} {}", entry.dest_range_start, entry.src_range_start, entry.range_len)?;
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traverse_maps;
+
+    fn entry(dest_range_start: u64, src_range_start: u64, range_len: u64) -> GardeningMapEntry {
+        GardeningMapEntry { dest_range_start, src_range_start, range_len }
+    }
+
+    #[test]
+    fn composed_map_matches_each_map_in_turn() {
+        let maps = vec![
+            vec![entry(50, 98, 2), entry(52, 50, 48)],
+            vec![entry(0, 15, 37), entry(37, 52, 2), entry(39, 0, 15)],
+        ];
+        let composed = PiecewiseMap::compose(&maps);
+        for seed in 0..120 {
+            assert_eq!(composed.get(seed), traverse_maps(&maps, seed), "seed {}", seed);
+        }
+        let inverse = composed.inverse().expect("the maps are one to one");
+        for seed in 0..120 {
+            assert_eq!(inverse.get(composed.get(seed)), seed);
+            assert_eq!(composed.preimage(composed.get(seed)), vec![seed]);
+        }
+        assert_eq!(composed.to_string(), "39 0 15\n0 15 35\n37 50 2\n54 52 46\n35 98 2\n");
+    }
+
+    #[test]
+    fn overlapping_destinations_cannot_be_inverted() {
+        let map = PiecewiseMap::from_map(&vec![entry(0, 10, 5)]);
+        assert_eq!(map.preimage(2), vec![2, 12]);
+        assert_eq!(map.map_range(8..12), vec![8..10, 0..2]);
+        assert_eq!(map.map_range(12..12), vec![]);
+        assert_eq!(map.inverse(), None);
+    }
+}
@@ -0,0 +1,199 @@
+//! Checks for almanac maps that don't describe a one to one mapping.
+//!
+//! The puzzle's maps are meant to shuffle whole ranges of values around
+//! without losing or doubling any, but nothing in the format enforces that.
+//! [`validate`] looks at each stage separately and lists what is wrong with
+//! it, so that generated inputs can be checked before they are used.
+
+use std::fmt;
+use std::ops::Range;
+
+use crate::{Almanac, GardeningMapEntry, PiecewiseMap, Stage};
+
+
+/// Something wrong with one stage of an almanac. Entries are numbered from 1
+/// in the order they appear under the stage's header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// Two entries both map the values in `values`. The first one wins.
+    Overlap {
+        /// The earlier entry.
+        first: usize,
+        /// The later entry.
+        second: usize,
+        /// The values both entries map.
+        values: Range<u64>,
+    },
+    /// An entry's source or destination range runs past `u64::MAX`. The
+    /// other checks ignore the entry.
+    Overflow {
+        /// The entry.
+        entry: usize,
+    },
+    /// More than one value maps to each of the values in `values`.
+    DuplicateDestination {
+        /// The values reached more than once.
+        values: Range<u64>,
+    },
+    /// Nothing maps to the values in `values`.
+    Unreachable {
+        /// The values never reached.
+        values: Range<u64>,
+    },
+}
+
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::Overlap { first, second, values } => {
+                write!(f, "entries {} and {} both map {:?}", first, second, values)
+            }
+            Problem::Overflow { entry } => write!(f, "entry {} runs past {}", entry, u64::MAX),
+            Problem::DuplicateDestination { values } => write!(f, "more than one value maps to {:?}", values),
+            Problem::Unreachable { values } => write!(f, "nothing maps to {:?}", values),
+        }
+    }
+}
+
+
+/// The problems found in one stage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageReport {
+    /// The stage's name, such as `seed-to-soil`.
+    pub name: String,
+    /// What is wrong with it, if anything.
+    pub problems: Vec<Problem>,
+}
+
+
+/// The problems found in every stage of an almanac.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// A report for each stage, in order.
+    pub stages: Vec<StageReport>,
+}
+
+
+impl ValidationReport {
+    /// Whether no stage has any problems.
+    pub fn is_valid(&self) -> bool {
+        self.stages.iter().all(|stage| stage.problems.is_empty())
+    }
+}
+
+
+/// Writes a line for each stage followed by a line for each of its problems.
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for stage in &self.stages {
+            match stage.problems.len() {
+                0 => writeln!(f, "{}: ok", stage.name)?,
+                1 => writeln!(f, "{}: 1 problem", stage.name)?,
+                n => writeln!(f, "{}: {} problems", stage.name, n)?,
+            }
+            for problem in &stage.problems {
+                writeln!(f, "  {}", problem)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+
+/// Check every stage of `almanac`.
+pub fn validate(almanac: &Almanac) -> ValidationReport {
+    ValidationReport { stages: almanac.stages.iter().map(validate_stage).collect() }
+}
+
+
+/// Check one stage.
+pub fn validate_stage(stage: &Stage) -> StageReport {
+    let mut problems = vec![];
+    // the entries that don't overflow, each with its number
+    let mut usable: Vec<(usize, &GardeningMapEntry)> = vec![];
+    for (i, entry) in stage.map.iter().enumerate() {
+        if entry.overflows() {
+            problems.push(Problem::Overflow { entry: i + 1 });
+        } else {
+            usable.push((i + 1, entry));
+        }
+    }
+    for (i, (first, first_entry)) in usable.iter().enumerate() {
+        for (second, second_entry) in &usable[i + 1..] {
+            let (first_range, second_range) = (first_entry.src_range(), second_entry.src_range());
+            let values = first_range.start.max(second_range.start)..first_range.end.min(second_range.end);
+            if !values.is_empty() {
+                problems.push(Problem::Overlap { first: *first, second: *second, values });
+            }
+        }
+    }
+    // building the map ignores overflowing entries too
+    problems.extend(destination_problems(&PiecewiseMap::from_map(&stage.map)));
+    StageReport { name: format!("{}-to-{}", stage.from, stage.to), problems }
+}
+
+
+/// The values `map` reaches more than once or not at all.
+fn destination_problems(map: &PiecewiseMap) -> Vec<Problem> {
+    let mut destinations: Vec<Range<u64>> = map.segments().iter().map(|segment| segment.dest()).collect();
+    destinations.sort_unstable_by_key(|range| range.start);
+    let mut problems = vec![];
+    let mut reached = 0;
+    for range in destinations {
+        if range.start > reached {
+            problems.push(Problem::Unreachable { values: reached..range.start });
+        } else if range.start < reached {
+            problems.push(Problem::DuplicateDestination { values: range.start..range.end.min(reached) });
+        }
+        reached = reached.max(range.end);
+    }
+    if reached < u64::MAX {
+        problems.push(Problem::Unreachable { values: reached..u64::MAX });
+    }
+    problems
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_almanac;
+
+    #[test]
+    fn problems_are_reported_per_stage() {
+        let input =
+"seeds: 79
+
+seed-to-soil map:
+10 0 5
+20 3 5
+18446744073709551610 2 10
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15";
+        // the third entry overlaps the others, but only its overflow is
+        // reported
+        let report = validate(&parse_almanac(input).expect("invalid input"));
+        assert!(!report.is_valid());
+        assert_eq!(report.stages[0].problems, vec![
+            Problem::Overflow { entry: 3 },
+            Problem::Overlap { first: 1, second: 2, values: 3..5 },
+            Problem::Unreachable { values: 0..8 },
+            Problem::DuplicateDestination { values: 10..15 },
+            Problem::DuplicateDestination { values: 22..25 },
+        ]);
+        assert!(report.stages[1].problems.is_empty());
+        assert_eq!(report.to_string(),
+"seed-to-soil: 5 problems
+  entry 3 runs past 18446744073709551615
+  entries 1 and 2 both map 3..5
+  nothing maps to 0..8
+  more than one value maps to 10..15
+  more than one value maps to 22..25
+soil-to-fertilizer: ok
+");
+    }
+}
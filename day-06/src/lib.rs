@@ -0,0 +1,241 @@
+//! Day 6: Wait For It
+//!
+//! Holding the button on a toy boat for longer makes it go faster but leaves
+//! less time to travel. We count the ways to beat each race's record.
+
+#![warn(missing_docs)]
+
+pub mod race;
+pub mod report;
+
+use std::ops::RangeInclusive;
+
+use aoc_common::parse::{parse_labelled_numbers, strip_label};
+use aoc_common::{AocError, Solution};
+
+pub use race::Race;
+pub use report::RaceReport;
+
+
+/* algebraic solution
+distance = speed * time
+time = total_time - button_time
+speed = button_time
+
+distance = button_time * (total_time - button_time)
+
+distance = (button_time * total_time) - (button_time * button_time)
+
+distance + button_time^2 = button_time * total_time
+
+button_time^2 - (total_time * button_time) + distance = 0
+
+ax^2 + bx + c = 0
+
+a = 1
+x = button_time
+b = -total_time
+c = distance
+
+x = ( -b +/- sqrt(b^2 - 4ac) ) / 2a
+
+*/
+/// Whether holding the button for `button_time` goes further than
+/// `distance` in a race lasting `total_time`.
+pub fn beats_record(button_time: u64, total_time: u64, distance: u64) -> bool {
+    button_time <= total_time && button_time as u128 * (total_time - button_time) as u128 > distance as u128
+}
+
+
+/// The shortest to longest button times that beat `distance`, or `None` if
+/// none do.
+///
+/// This is exact for any inputs: the roots are found with an integer square
+/// root, working in `u128` so that nothing overflows, and then nudged to the
+/// first whole button time that wins. The winning times are symmetric about
+/// `total_time / 2`, so the longest follows from the shortest.
+pub fn winning_button_times(total_time: u64, distance: u64) -> Option<RangeInclusive<u64>> {
+    let (t, d) = (total_time as u128, distance as u128);
+    let discriminant = (t * t).checked_sub(4 * d)?;
+    // the smaller root, rounded down, is within one of the shortest winning
+    // time
+    let mut min = ((t - discriminant.isqrt()) / 2) as u64;
+    while !beats_record(min, total_time, distance) {
+        if min >= total_time / 2 {
+            return None;
+        }
+        min += 1;
+    }
+    while min > 0 && beats_record(min - 1, total_time, distance) {
+        min -= 1;
+    }
+    Some(min..=total_time - min)
+}
+
+
+/// The number of button times that beat the record distance.
+pub fn get_num_race_options(time: u64, record_distance: u64) -> u64 {
+    let options = Race::new(time, record_distance).count_winning_hold_times();
+    log::trace!("time {} distance {}: {} options", time, record_distance, options);
+    options
+}
+
+
+/// Read the digits on a line as a single number, ignoring the spaces between
+/// them.
+pub fn part2_parse_number_from_str(s: &str) -> Result<u64, AocError> {
+    let digits = s.chars().filter(|c| !c.is_whitespace()).collect::<String>();
+    if digits.is_empty() {
+        return Err(AocError::missing(s, "a number"));
+    }
+    digits.parse::<u64>().map_err(|_| AocError::invalid_number(s, s.trim()))
+}
+
+
+/// Parse the races for part 1: a time and a record distance for each
+/// column of numbers.
+pub fn parse_races(input: &str) -> Result<Vec<Race>, AocError> {
+    let mut lines = input.lines();
+    let times = parse_labelled_numbers(lines.next().unwrap_or_default(), "Time:")
+        .map_err(|e| e.at_line(1))?;
+    let distances = parse_labelled_numbers(lines.next().unwrap_or_default(), "Distance:")
+        .map_err(|e| e.at_line(2))?;
+    if times.len() != distances.len() {
+        let reason = format!("there are {} times but {} distances", times.len(), distances.len());
+        return Err(AocError::unsolvable(reason));
+    }
+    Ok(times.into_iter()
+            .zip(distances)
+            .map(|(time, distance)| Race::new(time, distance))
+            .collect())
+}
+
+
+/// Parse the single race for part 2, ignoring the spaces between the
+/// numbers.
+pub fn parse_single_race(input: &str) -> Result<Race, AocError> {
+    let mut lines = input.lines();
+    let times_line = lines.next().unwrap_or_default();
+    let times_str = strip_label(times_line, "Time:").map_err(|e| e.at_line(1))?;
+    let time = part2_parse_number_from_str(times_str)
+        .map_err(|e| e.within(times_line, times_str).at_line(1))?;
+    let distances_line = lines.next().unwrap_or_default();
+    let distances_str = strip_label(distances_line, "Distance:").map_err(|e| e.at_line(2))?;
+    let distance = part2_parse_number_from_str(distances_str)
+        .map_err(|e| e.within(distances_line, distances_str).at_line(2))?;
+    Ok(Race::new(time, distance))
+}
+
+
+/// A report on each of the races for part 1.
+pub fn part1_reports(input: &str) -> Result<Vec<RaceReport>, AocError> {
+    Ok(parse_races(input)?.into_iter().map(RaceReport::new).collect())
+}
+
+
+/// A report on the single race for part 2.
+pub fn part2_report(input: &str) -> Result<RaceReport, AocError> {
+    Ok(RaceReport::new(parse_single_race(input)?))
+}
+
+
+/// Solve part 1: multiply together the number of ways to win each race.
+pub fn part1(input: &str) -> Result<u64, AocError> {
+    Ok(parse_races(input)?.iter()
+                          .map(|race| get_num_race_options(race.time, race.record))
+                          .product())
+}
+
+
+/// Solve part 2: the number of ways to win the single race we get by
+/// ignoring the spaces between the numbers.
+pub fn part2(input: &str) -> Result<u64, AocError> {
+    let race = parse_single_race(input)?;
+    Ok(get_num_race_options(race.time, race.record))
+}
+
+
+/// Solve both parts of the puzzle.
+pub fn both_parts(input: &str) -> Result<(u64, u64), AocError> {
+    Ok((part1(input)?, part2(input)?))
+}
+
+
+/// The [`Solution`] for day 6.
+pub struct Day06;
+
+
+impl Solution for Day06 {
+    fn day(&self) -> u8 {
+        6
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AocError> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, AocError> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn it_works() {
+        let input =
+"Time:      7  15   30
+Distance:  9  40  200";
+        assert_eq!(both_parts(input), Ok((288, 71503)));
+        //assert_eq!(part2(input), 30);
+    }
+
+    fn brute_force_race_options(time: u64, record_distance: u64) -> u64 {
+        (0..=time).filter(|button_time| beats_record(*button_time, time, record_distance)).count() as u64
+    }
+
+    #[test]
+    fn exact_square_roots_are_not_winners() {
+        // holding for 5 of 10 only ties a record of 25
+        assert_eq!(winning_button_times(10, 25), None);
+        assert_eq!(winning_button_times(10, 24), Some(5..=5));
+        assert_eq!(winning_button_times(30, 200), Some(11..=19));
+        assert_eq!(winning_button_times(u64::MAX, u64::MAX), Some(2..=u64::MAX - 2));
+    }
+
+    proptest! {
+        #[test]
+        fn matches_brute_force(time in 0u64..500, distance in 0u64..70_000) {
+            prop_assert_eq!(get_num_race_options(time, distance), brute_force_race_options(time, distance));
+        }
+
+        #[test]
+        fn boundaries_are_exact(time: u64, distance: u64) {
+            if let Some(times) = winning_button_times(time, distance) {
+                prop_assert!(beats_record(*times.start(), time, distance));
+                prop_assert!(beats_record(*times.end(), time, distance));
+                prop_assert!(*times.start() == 0 || !beats_record(times.start() - 1, time, distance));
+                prop_assert!(!beats_record(times.end() + 1, time, distance));
+            } else {
+                prop_assert!(!beats_record(time / 2, time, distance));
+            }
+        }
+    }
+
+    #[test]
+    fn missing_label_is_reported() {
+        let input =
+"Time:      7  15   30
+Distnce:  9  40  200";
+        assert_eq!(part1(input), Err(AocError::Unexpected {
+            line: 2,
+            column: 1,
+            text: "Distnce:".to_string(),
+            expected: "`Distance:`".to_string(),
+        }));
+    }
+}
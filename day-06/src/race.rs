@@ -0,0 +1,166 @@
+//! Races with more general boat physics than the puzzle's.
+//!
+//! In the puzzle each millisecond of holding the button adds one millimetre
+//! per millisecond of speed, and the boat keeps that speed until the race
+//! ends. A [`Race`] can also accelerate faster, have a top speed, and lose
+//! speed to friction once it is moving.
+
+use std::ops::RangeInclusive;
+
+use crate::winning_button_times;
+
+
+/// One race and the boat that runs it.
+///
+/// The distance travelled always rises and then falls as the hold time
+/// increases, possibly with a flat stretch at the top. The puzzle's rules
+/// are solved in closed form; anything else is solved by binary searching
+/// for the peak and then each side of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Race {
+    /// How long the race lasts, in milliseconds.
+    pub time: u64,
+    /// The distance to beat, in millimetres.
+    pub record: u64,
+    /// The speed gained for each millisecond the button is held.
+    pub acceleration: u64,
+    /// The fastest the boat can go, if there is a limit.
+    pub max_speed: Option<u64>,
+    /// The speed lost in each millisecond of moving, until the boat stops.
+    pub friction: u64,
+}
+
+
+impl Race {
+    /// A race run by the puzzle's rules.
+    pub fn new(time: u64, record: u64) -> Self {
+        Race { time, record, acceleration: 1, max_speed: None, friction: 0 }
+    }
+
+    /// The speed the boat leaves at after holding the button for
+    /// `hold_time`.
+    pub fn speed(&self, hold_time: u64) -> u64 {
+        let speed = self.acceleration.saturating_mul(hold_time);
+        self.max_speed.map_or(speed, |max_speed| speed.min(max_speed))
+    }
+
+    /// How far the boat goes after holding the button for `hold_time`.
+    pub fn distance(&self, hold_time: u64) -> u128 {
+        let Some(moving_time) = self.time.checked_sub(hold_time) else {
+            return 0;
+        };
+        let speed = self.speed(hold_time) as u128;
+        if self.friction == 0 {
+            return speed * moving_time as u128;
+        }
+        // the boat covers speed, speed - friction, ... in successive
+        // milliseconds until it stops or the race ends
+        let friction = self.friction as u128;
+        let steps = (moving_time as u128).min(speed.div_ceil(friction));
+        if steps == 0 {
+            return 0;
+        }
+        steps * speed - steps * (friction * (steps - 1)) / 2
+    }
+
+    /// Whether holding the button for `hold_time` beats the record.
+    pub fn beats_record(&self, hold_time: u64) -> bool {
+        self.distance(hold_time) > self.record as u128
+    }
+
+    fn is_puzzle_physics(&self) -> bool {
+        self.acceleration > 0 && self.max_speed.is_none() && self.friction == 0
+    }
+
+    /// The shortest hold time that goes as far as possible.
+    pub fn optimal_hold_time(&self) -> u64 {
+        if self.is_puzzle_physics() {
+            return self.time / 2;
+        }
+        // the first hold time after which holding longer doesn't help
+        first_where(0, self.time, |hold_time| {
+            hold_time == self.time || self.distance(hold_time + 1) <= self.distance(hold_time)
+        })
+    }
+
+    /// The shortest to longest hold times that beat the record, or `None`
+    /// if none do.
+    pub fn winning_hold_times(&self) -> Option<RangeInclusive<u64>> {
+        if self.is_puzzle_physics() {
+            // distances are all multiples of the acceleration, so beating
+            // the record is the same as beating it divided by the
+            // acceleration, rounded down
+            return winning_button_times(self.time, self.record / self.acceleration);
+        }
+        let optimal = self.optimal_hold_time();
+        if !self.beats_record(optimal) {
+            return None;
+        }
+        let min = first_where(0, optimal, |hold_time| self.beats_record(hold_time));
+        let max = first_where(optimal, self.time, |hold_time| {
+            hold_time == self.time || !self.beats_record(hold_time + 1)
+        });
+        Some(min..=max)
+    }
+
+    /// The number of hold times that beat the record.
+    pub fn count_winning_hold_times(&self) -> u64 {
+        self.winning_hold_times().map_or(0, |times| times.end() - times.start() + 1)
+    }
+}
+
+
+/// The first value in `low..=high` for which `predicate` holds, where it
+/// holds for every value after the first. `predicate(high)` must hold.
+fn first_where(mut low: u64, mut high: u64, predicate: impl Fn(u64) -> bool) -> u64 {
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if predicate(middle) {
+            high = middle;
+        } else {
+            low = middle + 1;
+        }
+    }
+    low
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn brute_force(race: &Race) -> (u64, Option<RangeInclusive<u64>>) {
+        let best = (0..=race.time).map(|hold_time| race.distance(hold_time)).max().unwrap();
+        let optimal = (0..=race.time).find(|hold_time| race.distance(*hold_time) == best).unwrap();
+        let winners: Vec<u64> = (0..=race.time).filter(|hold_time| race.beats_record(*hold_time)).collect();
+        let winning = winners.first().map(|min| *min..=*winners.last().unwrap());
+        (optimal, winning)
+    }
+
+    #[test]
+    fn capped_and_slowed_boats() {
+        let capped = Race { max_speed: Some(10), ..Race::new(30, 200) };
+        assert_eq!(capped.distance(12), 180);
+        assert_eq!(capped.optimal_hold_time(), 10);
+        assert_eq!(capped.winning_hold_times(), None);
+        let slowed = Race { friction: 2, ..Race::new(30, 50) };
+        // leaves at 8 and covers 8 + 6 + 4 + 2 before stopping
+        assert_eq!(slowed.distance(8), 20);
+        assert_eq!(slowed.optimal_hold_time(), 22);
+        assert_eq!(slowed.winning_hold_times(), Some(14..=28));
+        assert_eq!(Race::new(30, 200).count_winning_hold_times(), 9);
+        assert_eq!(Race { acceleration: 3, ..Race::new(30, 200) }.winning_hold_times(), Some(3..=27));
+    }
+
+    proptest! {
+        #[test]
+        fn matches_brute_force(time in 0u64..300, record in 0u64..20_000, acceleration in 0u64..5,
+                               max_speed in proptest::option::of(0u64..200), friction in 0u64..4) {
+            let race = Race { time, record, acceleration, max_speed, friction };
+            let (optimal, winning) = brute_force(&race);
+            prop_assert_eq!(race.optimal_hold_time(), optimal);
+            prop_assert_eq!(race.winning_hold_times(), winning);
+        }
+    }
+}
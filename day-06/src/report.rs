@@ -0,0 +1,133 @@
+//! Per-race breakdowns, to show where the answers come from.
+
+use std::ops::RangeInclusive;
+
+use aoc_common::table::{self, Align};
+use aoc_common::AocError;
+
+use crate::{part1_reports, part2_report, Race};
+
+
+/// What it takes to win one race.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RaceReport {
+    /// The race.
+    pub race: Race,
+    /// The shortest to longest hold times that beat the record, or `None`
+    /// if none do.
+    pub winning_hold_times: Option<RangeInclusive<u64>>,
+    /// The shortest hold time that goes as far as possible.
+    pub optimal_hold_time: u64,
+    /// How far the optimal hold time goes.
+    pub best_distance: u128,
+}
+
+
+impl RaceReport {
+    /// Work out the report for `race`.
+    pub fn new(race: Race) -> Self {
+        let optimal_hold_time = race.optimal_hold_time();
+        RaceReport {
+            race,
+            winning_hold_times: race.winning_hold_times(),
+            optimal_hold_time,
+            best_distance: race.distance(optimal_hold_time),
+        }
+    }
+
+    /// The number of hold times that beat the record.
+    pub fn ways_to_win(&self) -> u64 {
+        self.winning_hold_times.as_ref().map_or(0, |times| times.end() - times.start() + 1)
+    }
+
+    /// How much further than the record the best hold time goes, or 0 if
+    /// the record can't be beaten.
+    pub fn margin(&self) -> u128 {
+        self.best_distance.saturating_sub(self.race.record as u128)
+    }
+}
+
+
+/// Lay out `reports` as a table with a heading, a row per race.
+pub fn format_table(reports: &[RaceReport]) -> String {
+    let mut rows = vec![[
+        "race", "time", "record", "min hold", "max hold", "ways", "optimal hold", "best", "margin",
+    ].map(String::from)];
+    for (i, report) in reports.iter().enumerate() {
+        let (min, max) = match &report.winning_hold_times {
+            Some(times) => (times.start().to_string(), times.end().to_string()),
+            None => ("-".to_string(), "-".to_string()),
+        };
+        rows.push([
+            (i + 1).to_string(),
+            report.race.time.to_string(),
+            report.race.record.to_string(),
+            min,
+            max,
+            report.ways_to_win().to_string(),
+            report.optimal_hold_time.to_string(),
+            report.best_distance.to_string(),
+            report.margin().to_string(),
+        ]);
+    }
+    table::format_table(&rows, Align::Right)
+}
+
+
+/// Write `reports` as a JSON array with an object per race.
+pub fn to_json(reports: &[RaceReport]) -> String {
+    let objects: Vec<String> = reports.iter().map(|report| {
+        let winning_hold_times = match &report.winning_hold_times {
+            Some(times) => format!("{{\"min\": {}, \"max\": {}}}", times.start(), times.end()),
+            None => "null".to_string(),
+        };
+        format!("{{\"time\": {}, \"record\": {}, \"winning_hold_times\": {}, \"ways_to_win\": {}, \
+                 \"optimal_hold_time\": {}, \"best_distance\": {}, \"margin\": {}}}",
+                report.race.time,
+                report.race.record,
+                winning_hold_times,
+                report.ways_to_win(),
+                report.optimal_hold_time,
+                report.best_distance,
+                report.margin())
+    }).collect();
+    format!("[{}]", objects.join(", "))
+}
+
+
+/// The reports for both parts of `input`, as a table for each.
+pub fn tables_for(input: &str) -> Result<String, AocError> {
+    Ok(format!("Part 1:\n{}\nPart 2:\n{}", format_table(&part1_reports(input)?), format_table(&[part2_report(input)?])))
+}
+
+
+/// The reports for both parts of `input`, as a JSON object with an array for
+/// each.
+pub fn json_for(input: &str) -> Result<String, AocError> {
+    Ok(format!("{{\"part1\": {}, \"part2\": {}}}", to_json(&part1_reports(input)?), to_json(&[part2_report(input)?])))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_show_the_winning_interval() {
+        let races = [Race::new(7, 9), Race::new(30, 200), Race::new(4, 4)];
+        let reports: Vec<RaceReport> = races.into_iter().map(RaceReport::new).collect();
+        assert_eq!(reports[0].winning_hold_times, Some(2..=5));
+        assert_eq!((reports[0].optimal_hold_time, reports[0].best_distance, reports[0].margin()), (3, 12, 3));
+        assert_eq!(reports[2].ways_to_win(), 0);
+        assert_eq!(format_table(&reports),
+"race  time  record  min hold  max hold  ways  optimal hold  best  margin
+   1     7       9         2         5     4             3    12       3
+   2    30     200        11        19     9            15   225      25
+   3     4       4         -         -     0             2     4       0
+");
+        assert!(to_json(&reports).starts_with(
+            "[{\"time\": 7, \"record\": 9, \"winning_hold_times\": {\"min\": 2, \"max\": 5}, \"ways_to_win\": 4, "));
+        assert!(to_json(&reports).ends_with("\"winning_hold_times\": null, \"ways_to_win\": 0, \
+                                             \"optimal_hold_time\": 2, \"best_distance\": 4, \"margin\": 0}]"));
+    }
+}
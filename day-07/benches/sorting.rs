@@ -0,0 +1,46 @@
+//! Sorting hands by comparing them pairwise, which works out both hands'
+//! types for every comparison, against sorting by a key worked out once per
+//! hand.
+
+use aoc_common::random::XorShift;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+
+use day_07::{CamelCard, CamelCardsHand, Ruleset};
+
+
+// `num_hands` random hands of five, the same ones on every run
+fn deal(num_hands: usize) -> Vec<CamelCardsHand> {
+    let cards: Vec<CamelCard> = "23456789TJQKA".chars().map(CamelCard::from).collect();
+    let mut random = XorShift::default();
+    (0..num_hands).map(|_| CamelCardsHand { hand: (0..5).map(|_| cards[random.below(cards.len())]).collect() })
+                  .collect()
+}
+
+
+fn sorting(c: &mut Criterion) {
+    let hands = deal(100_000);
+    let rules = Ruleset::jokers();
+    let mut group = c.benchmark_group("sort 100000 hands");
+    group.sample_size(10);
+    group.bench_function("pairwise comparison", |b| {
+        b.iter_batched_ref(|| hands.clone(),
+                           |hands| hands.sort_by(|a, b| a.cmp_with(b, &rules)),
+                           BatchSize::LargeInput)
+    });
+    group.bench_function("packed key", |b| {
+        b.iter_batched(|| hands.clone(),
+                       |hands| {
+                           let mut keyed: Vec<(u128, CamelCardsHand)> = hands.into_iter()
+                                                                            .map(|hand| (hand.sort_key(&rules), hand))
+                                                                            .collect();
+                           keyed.sort_unstable_by_key(|(key, _)| *key);
+                           keyed
+                       },
+                       BatchSize::LargeInput)
+    });
+    group.finish();
+}
+
+
+criterion_group!(benches, sorting);
+criterion_main!(benches);
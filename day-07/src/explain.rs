@@ -0,0 +1,123 @@
+//! Explanations of how each hand was classified and ranked, to audit the
+//! total winnings.
+
+use aoc_common::table::{self, Align};
+use aoc_common::AocError;
+
+use crate::{parse_input, rank_hands, CamelCard, CamelCardsHand, CamelCardsHandListEntry, CamelCardsHandType, HandShape, Ruleset};
+
+
+/// How one hand was classified and what it won.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandExplanation {
+    /// The hand.
+    pub hand: CamelCardsHand,
+    /// The amount bid on the hand.
+    pub bid: u32,
+    /// How many of each card the hand holds, wild cards included, most
+    /// first and then strongest first.
+    pub counts: Vec<(CamelCard, u8)>,
+    /// The card the wild cards counted as, or `None` if the hand has no
+    /// wild cards or nothing but wild cards.
+    pub wild_became: Option<CamelCard>,
+    /// The hand's shape once the wild cards are counted.
+    pub shape: HandShape,
+    /// The hand's type once the wild cards are counted.
+    pub hand_type: CamelCardsHandType,
+    /// The hand's rank, counting from 1 for the weakest.
+    pub rank: usize,
+    /// The bid multiplied by the rank.
+    pub winnings: u64,
+}
+
+
+impl HandExplanation {
+    /// Explain `hand` under `rules`, given the rank it ended up with.
+    pub fn new(entry: CamelCardsHandListEntry, rank: usize, rules: &Ruleset) -> Self {
+        let mut counts: Vec<(CamelCard, u8)> = entry.hand.card_counts().into_iter().collect();
+        counts.sort_unstable_by_key(|(card, count)| std::cmp::Reverse((*count, rules.strength(*card))));
+        let has_wild = counts.iter().any(|(card, _)| rules.is_wild(*card));
+        let wild_became = counts.iter()
+                                .map(|(card, _)| *card)
+                                .find(|card| has_wild && !rules.is_wild(*card));
+        let shape = entry.hand.shape_with(rules);
+        HandExplanation {
+            counts,
+            wild_became,
+            hand_type: shape.hand_type(),
+            shape,
+            rank,
+            winnings: rank as u64 * entry.bid as u64,
+            hand: entry.hand,
+            bid: entry.bid,
+        }
+    }
+}
+
+
+/// Explain every hand in `hand_list` under `rules`, weakest first. The
+/// winnings add up to [`calculate_result_with`](crate::calculate_result_with).
+pub fn explain(hand_list: Vec<CamelCardsHandListEntry>, rules: &Ruleset) -> Vec<HandExplanation> {
+    rank_hands(hand_list, rules).into_iter()
+                                .enumerate()
+                                .map(|(i, entry)| HandExplanation::new(entry, i + 1, rules))
+                                .collect()
+}
+
+
+/// Lay out `explanations` as a table with a heading, a row per hand, and a
+/// total.
+pub fn format_table(explanations: &[HandExplanation]) -> String {
+    let mut rows = vec![["rank", "hand", "counts", "wild as", "type", "bid", "winnings"].map(String::from)];
+    for explanation in explanations {
+        let counts: Vec<String> = explanation.counts.iter().map(|(card, count)| format!("{}:{}", card, count)).collect();
+        rows.push([
+            explanation.rank.to_string(),
+            explanation.hand.to_string(),
+            counts.join(" "),
+            explanation.wild_became.map_or("-".to_string(), |card| card.to_string()),
+            explanation.shape.name(),
+            explanation.bid.to_string(),
+            explanation.winnings.to_string(),
+        ]);
+    }
+    let total: u64 = explanations.iter().map(|explanation| explanation.winnings).sum();
+    rows.push(["total", "", "", "", "", "", &total.to_string()].map(String::from));
+    table::format_table(&rows, Align::Left)
+}
+
+
+/// Explain both parts of `input`, as a table for each.
+pub fn tables_for(input: &str) -> Result<String, AocError> {
+    let part1 = explain(parse_input(input, false)?, &Ruleset::standard());
+    let part2 = explain(parse_input(input, false)?, &Ruleset::jokers());
+    Ok(format!("Part 1:\n{}\nPart 2:\n{}", format_table(&part1), format_table(&part2)))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculate_result_with;
+
+    #[test]
+    fn explanations_add_up() {
+        let input = "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483";
+        let explanations = explain(parse_input(input, false).unwrap(), &Ruleset::jokers());
+        let total: u64 = explanations.iter().map(|explanation| explanation.winnings).sum();
+        assert_eq!(total, calculate_result_with(parse_input(input, false).unwrap(), &Ruleset::jokers()));
+        assert_eq!(explanations[4].hand.to_string(), "KTJJT");
+        assert_eq!(explanations[4].counts, vec![(CamelCard::C10, 2), (CamelCard::J, 2), (CamelCard::K, 1)]);
+        assert_eq!(explanations[4].wild_became, Some(CamelCard::C10));
+        assert_eq!(explanations[4].hand_type, CamelCardsHandType::FourOfAKind);
+        assert_eq!(format_table(&explanations),
+"rank   hand   counts           wild as  type            bid  winnings
+1      32T3K  3:2 K:1 T:1 2:1  -        pair            765  765
+2      KK677  K:2 7:2 6:1      -        two pairs       28   56
+3      T55J5  5:3 T:1 J:1      5        four of a kind  684  2052
+4      QQQJA  Q:3 A:1 J:1      Q        four of a kind  483  1932
+5      KTJJT  T:2 J:2 K:1      T        four of a kind  220  1100
+total                                                        5905
+");
+    }
+}
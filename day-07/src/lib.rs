@@ -0,0 +1,431 @@
+//! Day 7: Camel Cards
+//!
+//! A simplified game of poker: each hand of five cards is ranked by type and
+//! then card by card, and wins its bid multiplied by its rank. Hands of other
+//! sizes are ranked by their [`HandShape`], and [`poker`] plays the classic
+//! game with suits, straights and flushes.
+
+#![warn(missing_docs)]
+
+pub mod explain;
+pub mod poker;
+pub mod rules;
+pub mod shape;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::cmp::Ordering;
+
+use aoc_common::parse::{parse_lines, parse_number};
+use aoc_common::{AocError, Solution};
+
+pub use rules::{Ruleset, TieBreak};
+pub use shape::HandShape;
+
+
+/// A single card, in ascending order of strength. `None` stands for a
+/// character that isn't a card.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum CamelCard {
+    Joker, C2, C3, C4, C5, C6, C7, C8, C9, C10,
+    J, Q, K, A, None
+}
+
+
+// note that we can compare these with each other because they
+// get a discriminant value by default:
+// https://doc.rust-lang.org/reference/items/enumerations.html
+impl From<char> for CamelCard {
+    fn from(value: char) -> Self {
+        match value {
+            '2' => CamelCard::C2,
+            '3' => CamelCard::C3,
+            '4' => CamelCard::C4,
+            '5' => CamelCard::C5,
+            '6' => CamelCard::C6,
+            '7' => CamelCard::C7,
+            '8' => CamelCard::C8,
+            '9' => CamelCard::C9,
+            'T' => CamelCard::C10,
+            'J' => CamelCard::J,
+            'Q' => CamelCard::Q,
+            'K' => CamelCard::K,
+            'A' => CamelCard::A,
+            _ => CamelCard::None,
+        }
+    }
+}
+
+
+impl fmt::Display for CamelCard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            CamelCard::Joker | CamelCard::J => 'J',
+            CamelCard::C10 => 'T',
+            CamelCard::Q => 'Q',
+            CamelCard::K => 'K',
+            CamelCard::A => 'A',
+            CamelCard::None => '?',
+            card => char::from(b'0' + *card as u8 + 1),
+        };
+        write!(f, "{}", c)
+    }
+}
+
+
+/// The type of a hand, in ascending order of strength.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CamelCardsHandType {
+    /// Five different cards.
+    HighCard,
+    /// Two of one card.
+    Pair,
+    /// Two pairs of different cards.
+    TwoPairs,
+    /// Three of one card.
+    ThreeOfAKind,
+    /// Three of one card and two of another.
+    FullHouse,
+    /// Four of one card.
+    FourOfAKind,
+    /// Five of one card.
+    FiveOfAKind,
+}
+
+
+/// The most cards a hand can hold, so that [`CamelCardsHand::sort_key`]
+/// can pack a hand into a `u128`.
+pub const MAX_HAND_SIZE: usize = 15;
+
+
+/// A hand of up to [`MAX_HAND_SIZE`] cards, ordered first by type and then
+/// card by card. The puzzle deals five.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CamelCardsHand {
+    /// The cards in the order they were dealt.
+    pub hand: Vec<CamelCard>,
+}
+
+
+impl CamelCardsHand {
+    /// How many of each card the hand holds.
+    pub fn card_counts(&self) -> HashMap<CamelCard, u8> {
+        let mut counts = HashMap::new();
+        for c in &self.hand {
+            *counts.entry(*c).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// The type of the hand, with any jokers counted as whichever card
+    /// makes the strongest hand.
+    pub fn hand_type(&self) -> CamelCardsHandType {
+        self.hand_type_with(&Ruleset::standard())
+    }
+
+    /// The type of the hand under `rules`, with any wild cards counted as
+    /// whichever card makes the strongest hand.
+    pub fn hand_type_with(&self, rules: &Ruleset) -> CamelCardsHandType {
+        self.shape_with(rules).hand_type()
+    }
+
+    /// The shape of the hand under `rules`, with any wild cards counted as
+    /// whichever card makes the strongest hand. This works for any size of
+    /// hand.
+    pub fn shape_with(&self, rules: &Ruleset) -> HandShape {
+        // counted by discriminant rather than in a HashMap as this is called
+        // for every hand
+        let mut counts = [0u8; CamelCard::None as usize + 1];
+        for card in &self.hand {
+            counts[*card as usize] += 1;
+        }
+        let num_wild: u8 = rules.wild_cards.iter().map(|card| std::mem::take(&mut counts[*card as usize])).sum();
+        HandShape::from_counts(counts, num_wild)
+    }
+
+    /// A number that orders hands the same way as [`cmp_with`](Self::cmp_with),
+    /// so that it can be worked out once per hand before sorting. From the
+    /// top, there are 4 bits for each of the [`MAX_HAND_SIZE`] groups in the
+    /// hand's shape and then 4 bits for the strength of each card in
+    /// tie-break order, padded with zeros.
+    pub fn sort_key(&self, rules: &Ruleset) -> u128 {
+        let mut strengths: Vec<u128> = self.hand.iter().map(|card| rules.strength(*card) as u128).collect();
+        debug_assert!(self.hand.len() <= MAX_HAND_SIZE && strengths.iter().all(|strength| *strength < 16),
+                      "hand {} doesn't fit in a sort key", self);
+        match rules.tie_break {
+            TieBreak::DealtOrder => {}
+            TieBreak::StrongestFirst => strengths.sort_unstable_by(|a, b| b.cmp(a)),
+            TieBreak::None => strengths.clear(),
+        }
+        let shape = self.shape_with(rules);
+        let groups = shape.0.iter().map(|group| *group as u128);
+        groups.chain(std::iter::repeat(0))
+              .take(MAX_HAND_SIZE)
+              .chain(strengths.into_iter().chain(std::iter::repeat(0)).take(MAX_HAND_SIZE))
+              .fold(0, |key, nibble| key << 4 | nibble)
+    }
+
+    /// Compare two hands under `rules`: first by shape, which for five cards
+    /// is the same as by type, and then by the ruleset's tie-break.
+    pub fn cmp_with(&self, other: &Self, rules: &Ruleset) -> Ordering {
+        self.shape_with(rules)
+            .cmp(&other.shape_with(rules))
+            .then_with(|| rules.break_tie(&self.hand, &other.hand))
+    }
+}
+
+
+impl fmt::Display for CamelCardsHand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for card in &self.hand {
+            write!(f, "{}", card)?;
+        }
+        Ok(())
+    }
+}
+
+
+impl PartialOrd for CamelCardsHand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+
+impl Ord for CamelCardsHand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_with(other, &Ruleset::standard())
+    }
+}
+
+
+impl FromStr for CamelCardsHand {
+    type Err = AocError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut hand = Vec::new();
+        for (i, c) in s.char_indices() {
+            if hand.len() == MAX_HAND_SIZE {
+                return Err(AocError::unexpected(s, &s[i..], "end of hand"));
+            }
+            let card = CamelCard::from(c);
+            if card == CamelCard::None {
+                return Err(AocError::unexpected(s, &s[i..i + c.len_utf8()], "a card"));
+            }
+            hand.push(card);
+        }
+        if hand.is_empty() {
+            return Err(AocError::missing(s, "a card"));
+        }
+        Ok(CamelCardsHand { hand })
+    }
+}
+
+
+/// One line of the input: a hand and its bid.
+#[derive(Debug)]
+pub struct CamelCardsHandListEntry {
+    /// The hand.
+    pub hand: CamelCardsHand,
+    /// The amount bid on the hand.
+    pub bid: u32,
+}
+
+
+impl FromStr for CamelCardsHandListEntry {
+    type Err = AocError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.split_whitespace();
+        let Some(hand_str) = fields.next() else {
+            return Err(AocError::missing(s, "a hand"));
+        };
+        let hand = hand_str.parse::<CamelCardsHand>().map_err(|e| e.within(s, hand_str))?;
+        let Some(bid_str) = fields.next() else {
+            return Err(AocError::missing(s, "a bid"));
+        };
+        let bid = parse_number(s, bid_str)?;
+        if let Some(extra) = fields.next() {
+            return Err(AocError::unexpected(s, extra, "end of line"));
+        }
+
+        Ok(CamelCardsHandListEntry {
+            hand,
+            bid,
+        })
+    }
+}
+
+
+impl CamelCardsHandListEntry {
+    /// Parse as normal and then replace the jacks with jokers.
+    pub fn from_str_with_jokers(s: &str) -> Result<Self, AocError> {
+        let mut hand_list_entry: CamelCardsHandListEntry = s.parse()?;
+        hand_list_entry.replace_jacks_with_jokers();
+        Ok(hand_list_entry)
+    }
+
+    /// Turn every jack in the hand into a joker.
+    pub fn replace_jacks_with_jokers(&mut self) {
+        for card in self.hand.hand.iter_mut() {
+            if *card == CamelCard::J {
+                *card = CamelCard::Joker;
+            }
+        }
+    }
+}
+
+
+/// Parse every line of the input, with or without jokers.
+pub fn parse_input(input: &str, jokers: bool) -> Result<Vec<CamelCardsHandListEntry>, AocError> {
+    let mut hand_list: Vec<CamelCardsHandListEntry> = parse_lines(input)?;
+    if jokers {
+        for hand_list_entry in hand_list.iter_mut() {
+            hand_list_entry.replace_jacks_with_jokers();
+        }
+    }
+    Ok(hand_list)
+}
+
+
+/// The total winnings: each bid multiplied by the rank of its hand.
+pub fn calculate_result(hand_list: Vec<CamelCardsHandListEntry>) -> u64 {
+    calculate_result_with(hand_list, &Ruleset::standard())
+}
+
+
+/// The hands ordered from weakest to strongest under `rules`, so that a
+/// hand's rank is its position counting from 1.
+pub fn rank_hands(hand_list: Vec<CamelCardsHandListEntry>, rules: &Ruleset) -> Vec<CamelCardsHandListEntry> {
+    let mut keyed_hands: Vec<(u128, CamelCardsHandListEntry)> = hand_list.into_iter()
+                                                                        .map(|h| (h.hand.sort_key(rules), h))
+                                                                        .collect();
+    keyed_hands.sort_unstable_by_key(|(key, _)| *key);
+    keyed_hands.into_iter().map(|(_, h)| h).collect()
+}
+
+
+/// The total winnings with the hands ranked under `rules`.
+pub fn calculate_result_with(hand_list: Vec<CamelCardsHandListEntry>, rules: &Ruleset) -> u64 {
+    let mut result = 0;
+    for (i, h) in rank_hands(hand_list, rules).into_iter().enumerate() {
+        log::trace!("rank {}: {} bids {}", i + 1, h.hand, h.bid);
+        result += (i+1) * h.bid as usize;
+    }
+    result as u64
+}
+
+
+/// Solve part 1: the total winnings with `J` as a jack.
+pub fn part1(input: &str) -> Result<u64, AocError> {
+    Ok(calculate_result(parse_input(input, false)?))
+}
+
+
+/// Solve part 2: the total winnings with `J` as a joker.
+pub fn part2(input: &str) -> Result<u64, AocError> {
+    Ok(calculate_result_with(parse_input(input, false)?, &Ruleset::jokers()))
+}
+
+
+/// Solve both parts of the puzzle.
+pub fn both_parts(input: &str) -> Result<(u64, u64), AocError> {
+    Ok((part1(input)?, part2(input)?))
+}
+
+
+/// The [`Solution`] for day 7.
+pub struct Day07;
+
+
+impl Solution for Day07 {
+    fn day(&self) -> u8 {
+        7
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AocError> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, AocError> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn it_works() {
+        let input =
+"32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+        assert_eq!(both_parts(input), Ok((6440, 5905)));
+        //assert_eq!(part2(input), 30);
+    }
+
+    #[test]
+    fn rulesets() {
+        let hands = |input: &str| parse_input(input, false).expect("invalid input");
+        let input = "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483";
+        assert_eq!(calculate_result(parse_input(input, true).unwrap()), 5905);
+        // with tens wild too, KTJJT becomes five kings
+        let tens_wild = Ruleset::new("JT23456789QKA", "JT", TieBreak::DealtOrder).unwrap();
+        assert_eq!(hands("KTJJT 1")[0].hand.hand_type_with(&tens_wild), CamelCardsHandType::FiveOfAKind);
+        let aces_low = Ruleset::new("A23456789TJQK", "", TieBreak::DealtOrder).unwrap();
+        let [low, high] = [&hands("A2345 1")[0].hand, &hands("23456 1")[0].hand];
+        assert_eq!(low.cmp_with(high, &aces_low), Ordering::Less);
+        assert_eq!(low.cmp_with(high, &Ruleset::standard()), Ordering::Greater);
+        let strongest_first = Ruleset { tie_break: TieBreak::StrongestFirst, ..Ruleset::standard() };
+        let [a, b] = [&hands("2345A 1")[0].hand, &hands("KQJT9 1")[0].hand];
+        assert_eq!(a.cmp_with(b, &strongest_first), Ordering::Greater);
+        assert_eq!(a.cmp_with(b, &Ruleset::standard()), Ordering::Less);
+        assert_eq!(Ruleset::new("23X", "", TieBreak::None).unwrap_err().column(), 3);
+        assert_eq!(Ruleset::new("2222222222222234", "", TieBreak::DealtOrder).unwrap_err().column(), 2);
+        assert_eq!(Ruleset::new("23456789TJQKA2", "", TieBreak::DealtOrder).unwrap_err().column(), 14);
+    }
+
+    #[test]
+    fn sort_keys_agree_with_comparison() {
+        let hands: Vec<CamelCardsHand> = ["32T3K", "T55J5", "KK677", "KTJJT", "QQQJA", "JJJJJ", "2345A", "KQJT9"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        let strongest_first = Ruleset { tie_break: TieBreak::StrongestFirst, ..Ruleset::jokers() };
+        let no_tie_break = Ruleset { tie_break: TieBreak::None, ..Ruleset::standard() };
+        for rules in [Ruleset::standard(), Ruleset::jokers(), strongest_first, no_tie_break] {
+            for a in &hands {
+                for b in &hands {
+                    assert_eq!(a.sort_key(&rules).cmp(&b.sort_key(&rules)), a.cmp_with(b, &rules), "{:?} {:?}", a, b);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn hands_of_any_size() {
+        let hand = |s: &str| s.parse::<CamelCardsHand>().unwrap();
+        assert_eq!(hand("KKK22QQ").shape_with(&Ruleset::standard()).name(), "full house (3 + 2 + 2)");
+        assert_eq!(hand("KKJ").shape_with(&Ruleset::jokers()).name(), "three of a kind");
+        assert_eq!(hand("QQ22").cmp_with(&hand("KKK2"), &Ruleset::standard()), Ordering::Less);
+        assert!(hand("AKQJT98765432A").sort_key(&Ruleset::standard()) > hand("AKQJT98765432K").sort_key(&Ruleset::standard()));
+        assert_eq!("23456789TJQKA234".parse::<CamelCardsHand>().unwrap_err().column(), 16);
+    }
+
+    #[test]
+    fn bad_card_is_reported() {
+        let input =
+"32T3K 765
+T55X5 684";
+        assert_eq!(part1(input), Err(AocError::Unexpected {
+            line: 2,
+            column: 4,
+            text: "X".to_string(),
+            expected: "a card".to_string(),
+        }));
+    }
+}
@@ -0,0 +1,214 @@
+//! Classic five card poker, with suits, straights and flushes.
+//!
+//! Cards are written as a rank and a suit, such as `TS` for the ten of
+//! spades, and rank in the same order as [`CamelCard`]. Hands of the same
+//! category are ordered by their largest groups first and then by rank, so
+//! that a pair of kings beats a pair of queens whatever else is held.
+
+use std::str::FromStr;
+
+use aoc_common::parse::{parse_lines, parse_number};
+use aoc_common::AocError;
+
+use crate::CamelCard;
+
+
+/// A card's suit.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Suit {
+    Clubs, Diamonds, Hearts, Spades
+}
+
+
+/// A card with a rank and a suit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PokerCard {
+    /// The card's rank.
+    pub rank: CamelCard,
+    /// The card's suit.
+    pub suit: Suit,
+}
+
+
+impl FromStr for PokerCard {
+    type Err = AocError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.char_indices();
+        let Some((_, rank_char)) = chars.next() else {
+            return Err(AocError::missing(s, "a rank"));
+        };
+        let rank = CamelCard::from(rank_char);
+        if rank == CamelCard::None {
+            return Err(AocError::unexpected(s, &s[..rank_char.len_utf8()], "a rank"));
+        }
+        let Some((i, suit_char)) = chars.next() else {
+            return Err(AocError::missing(s, "a suit"));
+        };
+        let suit = match suit_char.to_ascii_uppercase() {
+            'C' => Suit::Clubs,
+            'D' => Suit::Diamonds,
+            'H' => Suit::Hearts,
+            'S' => Suit::Spades,
+            _ => return Err(AocError::unexpected(s, &s[i..i + suit_char.len_utf8()], "a suit")),
+        };
+        if let Some((i, _)) = chars.next() {
+            return Err(AocError::unexpected(s, &s[i..], "end of card"));
+        }
+        Ok(PokerCard { rank, suit })
+    }
+}
+
+
+/// The categories of poker hand, weakest first.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PokerCategory {
+    HighCard, Pair, TwoPairs, ThreeOfAKind, Straight, Flush, FullHouse, FourOfAKind, StraightFlush
+}
+
+
+/// A hand of five poker cards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PokerHand {
+    /// The cards in the order they were dealt.
+    pub cards: [PokerCard; 5],
+}
+
+
+impl PokerHand {
+    /// The ranks that decide between hands of the same category: grouped
+    /// ranks first, largest group first, then highest rank first. In an
+    /// ace-low straight the ace counts below the two.
+    fn ranks(&self) -> [u8; 5] {
+        let mut counts = [0u8; CamelCard::None as usize];
+        for card in &self.cards {
+            counts[card.rank as usize] += 1;
+        }
+        let mut ranks: [u8; 5] = self.cards.map(|card| card.rank as u8);
+        ranks.sort_unstable_by(|a, b| counts[*b as usize].cmp(&counts[*a as usize]).then(b.cmp(a)));
+        if ranks == [CamelCard::A, CamelCard::C5, CamelCard::C4, CamelCard::C3, CamelCard::C2].map(|rank| rank as u8) {
+            ranks.rotate_left(1);
+            ranks[4] = 0;
+        }
+        ranks
+    }
+
+    /// The category of the hand.
+    pub fn category(&self) -> PokerCategory {
+        let ranks = self.ranks();
+        let is_flush = self.cards.iter().all(|card| card.suit == self.cards[0].suit);
+        let is_straight = ranks.windows(2).all(|pair| pair[0] == pair[1] + 1);
+        let group_sizes: Vec<usize> = ranks.chunk_by(|a, b| a == b).map(|group| group.len()).collect();
+        match (is_straight, is_flush, group_sizes.as_slice()) {
+            (true, true, _) => PokerCategory::StraightFlush,
+            (_, _, [4, ..]) => PokerCategory::FourOfAKind,
+            (_, _, [3, 2]) => PokerCategory::FullHouse,
+            (_, true, _) => PokerCategory::Flush,
+            (true, _, _) => PokerCategory::Straight,
+            (_, _, [3, ..]) => PokerCategory::ThreeOfAKind,
+            (_, _, [2, 2, ..]) => PokerCategory::TwoPairs,
+            (_, _, [2, ..]) => PokerCategory::Pair,
+            _ => PokerCategory::HighCard,
+        }
+    }
+
+    /// A number that orders hands from weakest to strongest: 4 bits for the
+    /// category and then 4 bits for each of the deciding ranks.
+    pub fn sort_key(&self) -> u32 {
+        self.ranks().iter().fold(self.category() as u32, |key, rank| key << 4 | *rank as u32)
+    }
+}
+
+
+impl FromStr for PokerHand {
+    type Err = AocError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = s.split_whitespace();
+        let mut cards = Vec::with_capacity(5);
+        for _ in 0..5 {
+            let Some(token) = tokens.next() else {
+                return Err(AocError::missing(s, "five cards"));
+            };
+            let card = token.parse::<PokerCard>().map_err(|e| e.within(s, token))?;
+            // there's only one of each card in the deck
+            if cards.contains(&card) {
+                return Err(AocError::unexpected(s, token, "a card not already dealt"));
+            }
+            cards.push(card);
+        }
+        if let Some(extra) = tokens.next() {
+            return Err(AocError::unexpected(s, extra, "end of hand"));
+        }
+        Ok(PokerHand { cards: cards.try_into().unwrap() })
+    }
+}
+
+
+/// One line of a poker input: five cards and then a bid.
+#[derive(Debug)]
+pub struct PokerHandListEntry {
+    /// The hand.
+    pub hand: PokerHand,
+    /// The amount bid on the hand.
+    pub bid: u32,
+}
+
+
+impl FromStr for PokerHandListEntry {
+    type Err = AocError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((hand_str, bid_str)) = s.trim_end().rsplit_once(char::is_whitespace) else {
+            return Err(AocError::missing(s, "a bid"));
+        };
+        let hand = hand_str.parse::<PokerHand>().map_err(|e| e.within(s, hand_str))?;
+        Ok(PokerHandListEntry { hand, bid: parse_number(s, bid_str)? })
+    }
+}
+
+
+/// The total winnings of a poker input: each bid multiplied by the rank of
+/// its hand.
+pub fn total_winnings(input: &str) -> Result<u64, AocError> {
+    let hand_list: Vec<PokerHandListEntry> = parse_lines(input)?;
+    let mut keyed_hands: Vec<(u32, u32)> = hand_list.iter().map(|h| (h.hand.sort_key(), h.bid)).collect();
+    keyed_hands.sort_unstable_by_key(|(key, _)| *key);
+    Ok(keyed_hands.iter().enumerate().map(|(i, (_, bid))| (i as u64 + 1) * *bid as u64).sum())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hand(s: &str) -> PokerHand {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn categories() {
+        assert_eq!(hand("TS JS QS KS AS").category(), PokerCategory::StraightFlush);
+        assert_eq!(hand("AH 2D 3C 4S 5S").category(), PokerCategory::Straight);
+        assert_eq!(hand("2H 4H 6H 8H TH").category(), PokerCategory::Flush);
+        assert_eq!(hand("KH KD 2C 2S KS").category(), PokerCategory::FullHouse);
+        assert_eq!(hand("KH KD 2C 2S 9S").category(), PokerCategory::TwoPairs);
+        assert_eq!(hand("QH KD AC 2S 3S").category(), PokerCategory::HighCard);
+        // the wheel is the lowest straight
+        assert!(hand("AH 2D 3C 4S 5S").sort_key() < hand("2H 3D 4C 5S 6S").sort_key());
+        assert!(hand("KH KD 2C 3S 4S").sort_key() > hand("QH QD AC KS JS").sort_key());
+    }
+
+    #[test]
+    fn winnings_and_errors() {
+        assert_eq!(total_winnings("2H 3D 4C 5S 6S 10\nAH AD 2C 3S 4S 100\n9C 9D 9H 2S 2D 1").unwrap(), 10 * 2 + 100 + 3);
+        assert_eq!("2H 3D 4X 5S 6S 10".parse::<PokerHandListEntry>().unwrap_err().column(), 8);
+        assert!("2H 3D 4C 5S 10".parse::<PokerHandListEntry>().is_err());
+        assert_eq!("AS 2D AS AS AS".parse::<PokerHand>(), Err(AocError::Unexpected {
+            line: 0,
+            column: 7,
+            text: "AS".to_string(),
+            expected: "a card not already dealt".to_string(),
+        }));
+        assert_eq!("AS 2D 3C 4C As".parse::<PokerHand>().unwrap_err().column(), 13);
+    }
+}
@@ -0,0 +1,128 @@
+//! The rules that decide which of two hands is stronger.
+//!
+//! The puzzle's two parts differ only in where `J` ranks and whether it is
+//! wild. A [`Ruleset`] captures that, along with how to break ties between
+//! hands of the same type, so that other variants need no new code.
+
+use std::cmp::Ordering;
+
+use aoc_common::AocError;
+
+use crate::CamelCard;
+
+
+/// How to order two hands of the same type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+    /// Compare the first cards dealt, then the second, and so on.
+    DealtOrder,
+    /// Compare the strongest cards in each hand, then the next strongest,
+    /// and so on.
+    StrongestFirst,
+    /// Hands of the same type are equal.
+    None,
+}
+
+
+/// The most cards a ruleset can rank.
+pub const MAX_CARDS: usize = 15;
+
+
+/// Which cards beat which, which cards are wild, and how ties are broken.
+///
+/// At most [`MAX_CARDS`] cards can be ranked, so that a card's strength fits in the 4
+/// bits [`CamelCardsHand::sort_key`](crate::CamelCardsHand::sort_key) gives
+/// it; that is every card there is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ruleset {
+    /// The cards from weakest to strongest. Any card not listed is weaker
+    /// than all of them.
+    pub card_order: Vec<CamelCard>,
+    /// The cards that count as whichever other card makes the strongest
+    /// hand. They still rank by `card_order` when breaking ties.
+    pub wild_cards: Vec<CamelCard>,
+    /// How to order hands of the same type.
+    pub tie_break: TieBreak,
+}
+
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        Ruleset::standard()
+    }
+}
+
+
+impl Ruleset {
+    /// The rules for part 1, which rank the cards as [`CamelCard`] does. A
+    /// [`CamelCard::Joker`] is wild, but only appears if one is put there.
+    pub fn standard() -> Self {
+        let mut card_order = vec![CamelCard::Joker];
+        card_order.extend(cards("23456789TJQKA"));
+        Ruleset { card_order, wild_cards: vec![CamelCard::Joker], tie_break: TieBreak::DealtOrder }
+    }
+
+    /// The rules for part 2: `J` is wild and is the weakest card.
+    pub fn jokers() -> Self {
+        Ruleset { card_order: cards("J23456789TQKA"), wild_cards: cards("J"), tie_break: TieBreak::DealtOrder }
+    }
+
+    /// Rules with the cards ranked in the order they appear in
+    /// `card_order`, such as `A23456789TJQK` for aces low, and the cards in
+    /// `wild_cards` wild. Each card can only be ranked once.
+    pub fn new(card_order: &str, wild_cards: &str, tie_break: TieBreak) -> Result<Self, AocError> {
+        let order = parse_cards(card_order)?;
+        for (i, (position, c)) in card_order.char_indices().enumerate() {
+            let text = &card_order[position..position + c.len_utf8()];
+            if i == MAX_CARDS {
+                return Err(AocError::unexpected(card_order, text, format!("at most {} cards", MAX_CARDS)));
+            }
+            if order[..i].contains(&order[i]) {
+                return Err(AocError::unexpected(card_order, text, "a card not already ranked"));
+            }
+        }
+        Ok(Ruleset { card_order: order, wild_cards: parse_cards(wild_cards)?, tie_break })
+    }
+
+    /// How strong `card` is: 0 for a card not in the order, otherwise its
+    /// position counting from 1.
+    pub fn strength(&self, card: CamelCard) -> usize {
+        self.card_order.iter().position(|c| *c == card).map_or(0, |i| i + 1)
+    }
+
+    /// Whether `card` is wild.
+    pub fn is_wild(&self, card: CamelCard) -> bool {
+        self.wild_cards.contains(&card)
+    }
+
+    /// Order two hands of the same type.
+    pub fn break_tie(&self, hand: &[CamelCard], other: &[CamelCard]) -> Ordering {
+        let strengths = |cards: &[CamelCard]| -> Vec<usize> {
+            let mut strengths: Vec<usize> = cards.iter().map(|card| self.strength(*card)).collect();
+            if self.tie_break == TieBreak::StrongestFirst {
+                strengths.sort_unstable_by(|a, b| b.cmp(a));
+            }
+            strengths
+        };
+        match self.tie_break {
+            TieBreak::None => Ordering::Equal,
+            _ => strengths(hand).cmp(&strengths(other)),
+        }
+    }
+}
+
+
+// the cards named by characters that are known to be valid
+fn cards(s: &str) -> Vec<CamelCard> {
+    s.chars().map(CamelCard::from).collect()
+}
+
+
+fn parse_cards(s: &str) -> Result<Vec<CamelCard>, AocError> {
+    s.char_indices()
+     .map(|(i, c)| match CamelCard::from(c) {
+         CamelCard::None => Err(AocError::unexpected(s, &s[i..i + c.len_utf8()], "a card")),
+         card => Ok(card),
+     })
+     .collect()
+}
@@ -0,0 +1,106 @@
+//! The shape of a hand of any size: how its cards group into matching sets.
+
+use crate::CamelCardsHandType;
+
+
+/// The sizes of the groups of matching cards in a hand, largest first, such
+/// as `[3, 2]` for a full house or `[2, 2, 1]` for two pairs.
+///
+/// For hands of the same size, comparing shapes compares the hands' types:
+/// more of a kind beats less, and then the second group decides, and so on.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HandShape(pub Vec<u8>);
+
+
+impl HandShape {
+    /// The shape of a hand holding `counts` of each non-wild card and
+    /// `num_wild` wild cards. The wild cards join the largest group, as that
+    /// makes the strongest hand.
+    pub fn from_counts(counts: impl IntoIterator<Item = u8>, num_wild: u8) -> Self {
+        let mut groups: Vec<u8> = counts.into_iter().filter(|count| *count > 0).collect();
+        groups.sort_unstable_by(|a, b| b.cmp(a));
+        match groups.first_mut() {
+            Some(largest) => *largest += num_wild,
+            None if num_wild > 0 => groups.push(num_wild),
+            None => {}
+        }
+        HandShape(groups)
+    }
+
+    /// The number of cards in the hand.
+    pub fn len(&self) -> usize {
+        self.0.iter().map(|group| *group as usize).sum()
+    }
+
+    /// Whether the hand has no cards.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The name of the shape, such as `three of a kind`, `two pairs` or
+    /// `full house (4 + 2)`. Single cards are left out, so a pair is a pair
+    /// however big the hand is.
+    pub fn name(&self) -> String {
+        let groups: Vec<u8> = self.0.iter().copied().filter(|group| *group > 1).collect();
+        match groups.as_slice() {
+            [] => "high card".to_string(),
+            [2] => "pair".to_string(),
+            [n] => format!("{} of a kind", number_name(*n as usize)),
+            pairs if pairs.iter().all(|group| *group == 2) => format!("{} pairs", number_name(pairs.len())),
+            _ => {
+                let sizes: Vec<String> = groups.iter().map(|group| group.to_string()).collect();
+                format!("full house ({})", sizes.join(" + "))
+            }
+        }
+    }
+
+    /// The nearest of the puzzle's five card hand types, judged by the two
+    /// largest groups.
+    pub fn hand_type(&self) -> CamelCardsHandType {
+        let largest = self.0.first().copied().unwrap_or(0);
+        let second = self.0.get(1).copied().unwrap_or(0);
+        match (largest, second) {
+            (5.., _) => CamelCardsHandType::FiveOfAKind,
+            (4, _) => CamelCardsHandType::FourOfAKind,
+            (3, 2..) => CamelCardsHandType::FullHouse,
+            (3, _) => CamelCardsHandType::ThreeOfAKind,
+            (2, 2) => CamelCardsHandType::TwoPairs,
+            (2, _) => CamelCardsHandType::Pair,
+            _ => CamelCardsHandType::HighCard,
+        }
+    }
+}
+
+
+fn number_name(n: usize) -> String {
+    match n {
+        2 => "two".to_string(),
+        3 => "three".to_string(),
+        4 => "four".to_string(),
+        5 => "five".to_string(),
+        6 => "six".to_string(),
+        _ => n.to_string(),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shapes_are_named_and_ordered() {
+        let shape = |counts: &[u8], num_wild| HandShape::from_counts(counts.iter().copied(), num_wild);
+        assert_eq!(shape(&[1, 0, 2, 1, 1], 0).name(), "pair");
+        assert_eq!(shape(&[2, 2, 2, 1], 0).name(), "three pairs");
+        assert_eq!(shape(&[4, 2], 0).name(), "full house (4 + 2)");
+        assert_eq!(shape(&[3, 3, 1], 0).name(), "full house (3 + 3)");
+        assert_eq!(shape(&[2, 1, 1], 2).name(), "four of a kind");
+        assert_eq!(shape(&[], 7).name(), "7 of a kind");
+        assert_eq!(shape(&[1, 1, 1, 1, 1], 0).name(), "high card");
+        assert_eq!(shape(&[3, 2], 0).hand_type(), CamelCardsHandType::FullHouse);
+        assert!(shape(&[3, 3, 1], 0) > shape(&[3, 2, 2], 0));
+        assert!(shape(&[2, 2, 2, 1], 0) < shape(&[3, 1, 1, 1, 1], 0));
+        assert_eq!(shape(&[3, 3, 1], 0).len(), 7);
+    }
+}
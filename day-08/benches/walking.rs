@@ -0,0 +1,54 @@
+//! Walking a network by looking up node names in a map, as part 1 first did,
+//! against walking the numbered [`Network`].
+
+use std::hint::black_box;
+
+use aoc_common::random::XorShift;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use day_08::{parse_network, Network};
+
+
+const NUM_STEPS: usize = 1_000_000;
+
+
+// a network of `num_nodes` nodes called AAA, AAB and so on, each leading to
+// two random nodes, with random instructions
+fn generate(num_nodes: usize, num_instructions: usize) -> String {
+    let mut random = XorShift::default();
+    let name = |i: usize| format!("{}{}{}", (b'A' + (i / 676) as u8) as char, (b'A' + (i / 26 % 26) as u8) as char,
+                                  (b'A' + (i % 26) as u8) as char);
+    let instructions: String = (0..num_instructions).map(|_| if random.below(2) == 0 { 'L' } else { 'R' }).collect();
+    let nodes: Vec<String> = (0..num_nodes)
+        .map(|i| format!("{} = ({}, {})", name(i), name(random.below(num_nodes)), name(random.below(num_nodes))))
+        .collect();
+    format!("{}\n\n{}", instructions, nodes.join("\n"))
+}
+
+
+fn walking(c: &mut Criterion) {
+    let input = generate(750, 300);
+    let mut group = c.benchmark_group("walk 1000000 steps");
+    group.sample_size(10);
+    group.bench_function("names in a map", |b| {
+        let (instructions, nodes) = parse_network(&input).unwrap();
+        b.iter(|| {
+            let mut node = "AAA";
+            for step in 0..NUM_STEPS {
+                let instruction = instructions.chars().nth(step % instructions.len()).unwrap();
+                let (left, right) = nodes[node];
+                node = if instruction == 'L' { left } else { right };
+            }
+            black_box(node)
+        })
+    });
+    group.bench_function("numbered network", |b| {
+        let network = Network::parse(&input).unwrap();
+        b.iter(|| black_box(network.walk(0).nth(NUM_STEPS - 1)))
+    });
+    group.finish();
+}
+
+
+criterion_group!(benches, walking);
+criterion_main!(benches);
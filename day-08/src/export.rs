@@ -0,0 +1,171 @@
+//! Export a network as a Graphviz DOT or Mermaid graph, to look at its
+//! shape.
+
+use std::collections::HashSet;
+
+use aoc_common::AocError;
+
+use crate::{Network, Walk};
+
+
+/// The formats a network can be exported in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    /// Graphviz DOT, written by [`to_dot`].
+    Dot,
+    /// A Mermaid flowchart, written by [`to_mermaid`].
+    Mermaid,
+}
+
+
+/// Which nodes and edges to pick out in an exported graph.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Highlights {
+    /// The nodes visited by a walk, in order. Each edge between consecutive
+    /// nodes is drawn more heavily.
+    pub path: Vec<u32>,
+    /// Nodes to mark as starts.
+    pub starts: Vec<u32>,
+    /// Nodes to mark as ends.
+    pub ends: Vec<u32>,
+}
+
+
+impl Highlights {
+    /// Nothing highlighted.
+    pub fn none() -> Self {
+        Highlights::default()
+    }
+
+    /// The puzzle's starts and ends, which are the nodes ending in `A` and
+    /// `Z`, and the part 1 walk from `AAA` to `ZZZ` if it gets there.
+    pub fn puzzle(network: &Network) -> Self {
+        let path = match (network.id("AAA"), network.id("ZZZ")) {
+            (Ok(start), Ok(end)) => match network.walk_until(start, |node| node == end) {
+                Walk::Reached(num_steps) => std::iter::once(start).chain(network.walk(start))
+                                                                   .take(num_steps as usize + 1)
+                                                                   .collect(),
+                Walk::Unreachable(_) => vec![],
+            },
+            _ => vec![],
+        };
+        Highlights {
+            path,
+            starts: network.ids_where(|name| name.ends_with('A')),
+            ends: network.ids_where(|name| name.ends_with('Z')),
+        }
+    }
+
+    fn path_edges(&self) -> HashSet<(u32, u32)> {
+        self.path.windows(2).map(|pair| (pair[0], pair[1])).collect()
+    }
+}
+
+
+// each distinct edge out of `node`, labelled with the directions that take
+// it, so that a node like `ZZZ = (ZZZ, ZZZ)` gets one self loop
+fn edges(network: &Network, node: u32) -> Vec<(u32, &'static str)> {
+    match network.adjacency[node as usize] {
+        [left, right] if left == right => vec![(left, "L/R")],
+        [left, right] => vec![(left, "L"), (right, "R")],
+    }
+}
+
+
+/// Write `network` in Graphviz DOT format.
+pub fn to_dot(network: &Network, highlights: &Highlights) -> String {
+    let path_edges = highlights.path_edges();
+    let mut dot = String::from("digraph network {\n");
+    for node in 0..network.len() as u32 {
+        let fill = if highlights.starts.contains(&node) {
+            ", style=filled, fillcolor=palegreen"
+        } else if highlights.ends.contains(&node) {
+            ", style=filled, fillcolor=lightcoral"
+        } else {
+            ""
+        };
+        dot.push_str(&format!("    \"{}\" [shape=circle{}];\n", network.name(node), fill));
+    }
+    for node in 0..network.len() as u32 {
+        for (neighbour, label) in edges(network, node) {
+            let style = if path_edges.contains(&(node, neighbour)) { ", color=red, penwidth=2" } else { "" };
+            dot.push_str(&format!("    \"{}\" -> \"{}\" [label=\"{}\"{}];\n",
+                                  network.name(node), network.name(neighbour), label, style));
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+
+/// Write `network` as a Mermaid flowchart.
+pub fn to_mermaid(network: &Network, highlights: &Highlights) -> String {
+    let path_edges = highlights.path_edges();
+    let mut mermaid = String::from("flowchart LR\n");
+    for node in 0..network.len() as u32 {
+        mermaid.push_str(&format!("    n{}[\"{}\"]\n", node, network.name(node)));
+    }
+    for node in 0..network.len() as u32 {
+        for (neighbour, label) in edges(network, node) {
+            let arrow = if path_edges.contains(&(node, neighbour)) { "==>" } else { "-->" };
+            mermaid.push_str(&format!("    n{} {}|{}| n{}\n", node, arrow, label, neighbour));
+        }
+    }
+    // `end` is a keyword in Mermaid, so the classes can't be called that
+    for (class, fill, nodes) in [("startNode", "#9f9", &highlights.starts), ("endNode", "#f99", &highlights.ends)] {
+        if !nodes.is_empty() {
+            let ids: Vec<String> = nodes.iter().map(|node| format!("n{}", node)).collect();
+            mermaid.push_str(&format!("    classDef {} fill:{}\n    class {} {}\n", class, fill, ids.join(","), class));
+        }
+    }
+    mermaid
+}
+
+
+/// Parse `input` and export its network in `format`, picking out the
+/// puzzle's [`Highlights`] if `highlight` is set.
+pub fn graph_for(input: &str, format: GraphFormat, highlight: bool) -> Result<String, AocError> {
+    let network = Network::parse(input)?;
+    let highlights = if highlight { Highlights::puzzle(&network) } else { Highlights::none() };
+    Ok(match format {
+        GraphFormat::Dot => to_dot(&network, &highlights),
+        GraphFormat::Mermaid => to_mermaid(&network, &highlights),
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exports() {
+        let network = Network::parse("LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap();
+        let highlights = Highlights::puzzle(&network);
+        assert_eq!(highlights.path, vec![0, 1, 0, 1, 0, 1, 2]);
+        assert_eq!(to_dot(&network, &highlights),
+"digraph network {
+    \"AAA\" [shape=circle, style=filled, fillcolor=palegreen];
+    \"BBB\" [shape=circle];
+    \"ZZZ\" [shape=circle, style=filled, fillcolor=lightcoral];
+    \"AAA\" -> \"BBB\" [label=\"L/R\", color=red, penwidth=2];
+    \"BBB\" -> \"AAA\" [label=\"L\", color=red, penwidth=2];
+    \"BBB\" -> \"ZZZ\" [label=\"R\", color=red, penwidth=2];
+    \"ZZZ\" -> \"ZZZ\" [label=\"L/R\"];
+}
+");
+        assert_eq!(to_mermaid(&network, &Highlights::none()),
+"flowchart LR
+    n0[\"AAA\"]
+    n1[\"BBB\"]
+    n2[\"ZZZ\"]
+    n0 -->|L/R| n1
+    n1 -->|L| n0
+    n1 -->|R| n2
+    n2 -->|L/R| n2
+");
+        assert!(to_mermaid(&network, &highlights).ends_with(
+            "    n1 ==>|R| n2\n    n2 -->|L/R| n2\n    classDef startNode fill:#9f9\n    class n0 startNode\n\
+             \x20   classDef endNode fill:#f99\n    class n2 endNode\n"));
+    }
+}
@@ -0,0 +1,217 @@
+//! Part 2: ghosts start on every node ending in `A` and all move at once,
+//! and we want the first step at which every one of them is on a node
+//! ending in `Z`.
+//!
+//! Walking them together takes far too long, so each ghost is walked alone
+//! until it is back on a node at the start of the instructions. From then on
+//! it goes round the same cycle forever, so the steps at which it is on an
+//! end node are a few one-off steps before the cycle and a few residues
+//! modulo the cycle length. The first step that suits every ghost comes from
+//! combining those residues with the Chinese remainder theorem.
+
+use crate::network::Repeat;
+use crate::Network;
+
+
+/// When one ghost is on an end node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GhostCycle {
+    /// The number of steps before the ghost first enters its cycle.
+    pub tail: u64,
+    /// The number of steps it takes to go round the cycle.
+    pub length: u64,
+    /// The steps before `tail` at which the ghost is on an end node, in
+    /// order.
+    pub tail_hits: Vec<u64>,
+    /// The steps from `tail` up to `tail + length` at which the ghost is on
+    /// an end node, in order. It is on one again every `length` steps after
+    /// each.
+    pub cycle_hits: Vec<u64>,
+}
+
+
+impl GhostCycle {
+    /// Walk a ghost from `start` until it repeats itself, noting when it is
+    /// on a node for which `is_end` holds.
+    pub fn find(network: &Network, start: u32, is_end: impl Fn(u32) -> bool) -> Self {
+        let mut hits = Vec::new();
+        let repeat = network.repeat_from(start, |step, node| {
+            if is_end(node) {
+                hits.push(step);
+            }
+            true
+        });
+        let Repeat { tail, length, .. } = repeat.expect("the walk only stops when it repeats");
+        let (tail_hits, cycle_hits) = hits.iter().partition(|hit| **hit < tail);
+        GhostCycle { tail, length, tail_hits, cycle_hits }
+    }
+
+    /// Whether the ghost is on an end node after `step` steps.
+    pub fn is_end_at(&self, step: u64) -> bool {
+        if step < self.tail {
+            return self.tail_hits.binary_search(&step).is_ok();
+        }
+        let offset = (step - self.tail) % self.length;
+        self.cycle_hits.binary_search(&(self.tail + offset)).is_ok()
+    }
+
+    /// The steps before `end` at which the ghost is on an end node, in
+    /// order.
+    pub fn hits_before(&self, end: u64) -> impl Iterator<Item = u64> + '_ {
+        let passes = (0..).map(|pass| pass * self.length).take_while(move |offset| self.tail + offset < end);
+        let cycle_hits = passes.flat_map(|offset| self.cycle_hits.iter().map(move |hit| hit + offset));
+        self.tail_hits.iter().copied().chain(cycle_hits).take_while(move |step| *step < end)
+    }
+}
+
+
+/// The first step at which every ghost is on an end node, if there is one.
+pub fn first_common_end(ghosts: &[GhostCycle]) -> Option<u64> {
+    // the ghosts with the fewest hits rule out the most steps, so start
+    // with them
+    let mut ghosts: Vec<&GhostCycle> = ghosts.iter().collect();
+    ghosts.sort_by_key(|ghost| ghost.tail_hits.len() + ghost.cycle_hits.len());
+    let longest_tail = ghosts.iter().map(|ghost| ghost.tail).max()?;
+    let longest_length = ghosts.iter().map(|ghost| ghost.length).max()?;
+    // trying each step up to a cycle past the longest tail covers every
+    // step before all the ghosts are in their cycles, and finds early
+    // answers without any arithmetic
+    let scan_end = longest_tail + longest_length;
+    let (fewest, others) = ghosts.split_first()?;
+    let early = fewest.hits_before(scan_end).find(|step| others.iter().all(|ghost| ghost.is_end_at(*step)));
+    if early.is_some() {
+        return early;
+    }
+    // after that every ghost is in its cycle, so each step that suits them
+    // all solves one congruence per ghost
+    first_in_cycles(&ghosts, (0, 1), scan_end as u128).and_then(|step| u64::try_from(step).ok())
+}
+
+
+// the first step from `from` on that satisfies `congruence` and puts every
+// ghost in `ghosts` on one of its cycle hits, trying each hit of the first
+// ghost in turn so that only one combination is held at a time
+fn first_in_cycles(ghosts: &[&GhostCycle], congruence: (u128, u128), from: u128) -> Option<u128> {
+    let Some((ghost, rest)) = ghosts.split_first() else {
+        let (residue, modulus) = congruence;
+        return Some(residue + from.saturating_sub(residue).div_ceil(modulus) * modulus);
+    };
+    let length = ghost.length as u128;
+    ghost.cycle_hits.iter()
+                    .filter_map(|hit| combine(congruence, (*hit as u128 % length, length)))
+                    .filter_map(|combined| first_in_cycles(rest, combined, from))
+                    .min()
+}
+
+
+/// The congruence satisfied by exactly the numbers that satisfy both `a` and
+/// `b`, each given as (residue, modulus), or `None` if no number does.
+fn combine((a, m): (u128, u128), (b, n): (u128, u128)) -> Option<(u128, u128)> {
+    let (g, p, _) = extended_gcd(m as i128, n as i128);
+    let difference = b as i128 - a as i128;
+    if difference % g != 0 {
+        return None;
+    }
+    let lcm = m / g as u128 * n;
+    // a + m * k is the answer, where m * k = difference (mod n), so k is
+    // difference / g times the inverse of m / g modulo n / g
+    let n_over_g = n as i128 / g;
+    let k = (difference / g % n_over_g * p % n_over_g).rem_euclid(n_over_g);
+    Some(((a + m * k as u128) % lcm, lcm))
+}
+
+
+/// (g, x, y) such that g = gcd(a, b) = a * x + b * y.
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        return (a, 1, 0);
+    }
+    let (g, x, y) = extended_gcd(b, a % b);
+    (g, y, x - a / b * y)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // walk every ghost together, for checking against
+    fn brute_force(input: &str, limit: usize) -> Option<u64> {
+        let network = Network::parse(input).unwrap();
+        let mut ghosts = network.ids_where(|name| name.ends_with('A'));
+        for (step, direction) in network.instructions.iter().cycle().take(limit).enumerate() {
+            if ghosts.iter().all(|node| network.name(*node).ends_with('Z')) {
+                return Some(step as u64);
+            }
+            for node in ghosts.iter_mut() {
+                *node = network.next(*node, *direction);
+            }
+        }
+        None
+    }
+
+    fn solve(input: &str) -> Option<u64> {
+        let network = Network::parse(input).unwrap();
+        let ghosts: Vec<GhostCycle> = network.ids_where(|name| name.ends_with('A'))
+                                             .into_iter()
+                                             .map(|start| GhostCycle::find(&network, start, |node| network.name(node).ends_with('Z')))
+                                             .collect();
+        first_common_end(&ghosts)
+    }
+
+    #[test]
+    fn cycles_that_do_not_line_up() {
+        // 11A has a one step tail and then hits every 2 steps; 22A hits
+        // first at step 1 and then every 3 steps
+        let input = "L\n\n11A = (11B, XXX)\n11B = (11Z, XXX)\n11Z = (11B, XXX)\n\
+                     22A = (22Z, XXX)\n22Z = (22B, XXX)\n22B = (22C, XXX)\n22C = (22Z, XXX)\nXXX = (XXX, XXX)";
+        let network = Network::parse(input).unwrap();
+        let ghost = GhostCycle::find(&network, network.id("11A").unwrap(), |node| network.name(node).ends_with('Z'));
+        assert_eq!(ghost, GhostCycle { tail: 1, length: 2, tail_hits: vec![], cycle_hits: vec![2] });
+        // with two instructions the repeat is only noticed at a whole pass,
+        // a step later than it starts
+        let input_lr = "LR\n\n11A = (11B, 11B)\n11B = (11Z, 11Z)\n11Z = (11B, 11B)\n\
+                        22A = (22Z, 22Z)\n22Z = (22B, 22B)\n22B = (22C, 22C)\n22C = (22Z, 22Z)";
+        let network = Network::parse(input_lr).unwrap();
+        let ghost = GhostCycle::find(&network, network.id("11A").unwrap(), |node| network.name(node).ends_with('Z'));
+        assert_eq!(ghost, GhostCycle { tail: 2, length: 2, tail_hits: vec![], cycle_hits: vec![2] });
+        assert_eq!(solve(input_lr), brute_force(input_lr, 100));
+        assert_eq!(solve(input), Some(4));
+        assert_eq!(solve(input), brute_force(input, 100));
+        // 33A is only ever on an end node once, before its cycle
+        let input = format!("{}\n33A = (33B, XXX)\n33B = (33C, XXX)\n33C = (33D, XXX)\n\
+                             33D = (33Z, XXX)\n33Z = (33E, XXX)\n33E = (33E, XXX)", input);
+        assert_eq!(solve(&input), Some(4));
+        assert_eq!(solve(&input), brute_force(&input, 100));
+        let input = input.replace("33C = (33D", "33C = (33Z");
+        assert_eq!(solve(&input), None);
+        assert_eq!(combine((2, 4), (1, 6)), None);
+        assert_eq!(ghost.hits_before(9).collect::<Vec<_>>(), vec![2, 4, 6, 8]);
+        assert_eq!(combine((3, 4), (1, 6)), Some((7, 12)));
+    }
+
+    // a ghost for each of `rings`, which are a cycle length and the
+    // positions on the cycle that end in `Z`
+    fn rings(rings: &[(usize, Vec<usize>)]) -> String {
+        let mut lines = vec!["L".to_string(), String::new()];
+        for (ghost, (length, ends)) in rings.iter().enumerate() {
+            let name = |i: usize| format!("G{}N{}{}", ghost, i, if ends.contains(&i) { "Z" } else { "X" });
+            lines.push(format!("G{}A = ({1}, {1})", ghost, name(0)));
+            lines.extend((0..*length).map(|i| format!("{0} = ({1}, {1})", name(i), name((i + 1) % length))));
+        }
+        lines.join("\n")
+    }
+
+    #[test]
+    fn many_hits_per_ghost() {
+        // the first common end comes after a cycle of the longest ring, so
+        // the hits have to be combined
+        let input = rings(&[(4, vec![0, 1]), (5, vec![2, 3]), (7, vec![4, 5]), (9, vec![6, 7, 8])]);
+        assert_eq!(solve(&input), Some(34));
+        assert_eq!(solve(&input), brute_force(&input, 100));
+        // nearly every node is an end, on long prime cycles; combining every
+        // hit of every ghost would take billions of combinations
+        let input = rings(&[97, 89, 83, 79, 73, 71].map(|length| (length, (1..length).collect())));
+        assert_eq!(solve(&input), Some(2));
+    }
+}
@@ -0,0 +1,254 @@
+//! Day 8: Haunted Wasteland
+//!
+//! A network of nodes, each with a left and right neighbour, is navigated by
+//! following a repeating list of left/right instructions.
+
+#![warn(missing_docs)]
+
+pub mod export;
+pub mod ghosts;
+pub mod network;
+
+use std::collections::HashMap;
+
+use aoc_common::parse::{blocks, split_pair};
+use aoc_common::{AocError, Solution};
+
+pub use export::{graph_for, to_dot, to_mermaid, GraphFormat, Highlights};
+pub use ghosts::{first_common_end, GhostCycle};
+pub use network::{Direction, Loop, Network, Walk};
+
+
+/// Parse a line like `AAA = (BBB, CCC)` into the node and its left and
+/// right neighbours. Names can be any length of letters and digits, and
+/// there can be any amount of space between the parts.
+pub fn parse_line(line: &str) -> Result<(&str, &str, &str), AocError> {
+    let (node, neighbours) = split_pair(line, '=')?;
+    let node = parse_name(line, node)?;
+    let neighbours = neighbours.trim_start();
+    let Some(neighbours) = neighbours.strip_prefix('(') else {
+        let found = neighbours.get(..1).unwrap_or(neighbours);
+        return Err(AocError::unexpected(line, found, "`(`"));
+    };
+    let (neighbours, after) = split_pair(neighbours, ')').map_err(|e| e.within(line, neighbours))?;
+    if !after.trim().is_empty() {
+        return Err(AocError::unexpected(line, after.trim(), "end of line"));
+    }
+    let (left, right) = split_pair(neighbours, ',').map_err(|e| e.within(line, neighbours))?;
+    Ok((node, parse_name(line, left)?, parse_name(line, right)?))
+}
+
+
+// a node name surrounded by any amount of space, where `text` is a slice of
+// `line`
+fn parse_name<'a>(line: &str, text: &'a str) -> Result<&'a str, AocError> {
+    let name = text.trim();
+    if name.is_empty() {
+        return Err(AocError::unexpected(line, text, "a node name"));
+    }
+    if let Some((i, c)) = name.char_indices().find(|(_, c)| !c.is_alphanumeric()) {
+        return Err(AocError::unexpected(line, &name[i..i + c.len_utf8()], "a letter or digit"));
+    }
+    Ok(name)
+}
+
+
+/// Decode a list of instructions such as `LLR`. There must be at least one.
+pub fn parse_instructions(instructions: &str) -> Result<Vec<Direction>, AocError> {
+    if instructions.is_empty() {
+        return Err(AocError::missing(instructions, "a list of instructions"));
+    }
+    instructions.char_indices()
+                .map(|(i, c)| match c {
+                    'L' => Ok(Direction::Left),
+                    'R' => Ok(Direction::Right),
+                    _ => Err(AocError::unexpected(instructions, &instructions[i..i + c.len_utf8()], "`L` or `R`")),
+                })
+                .collect()
+}
+
+
+/// A map from each node to the nodes to its left and right.
+pub type Nodes<'a> = HashMap<&'a str, (&'a str, &'a str)>;
+
+
+/// Parse the input into the instructions and the nodes. Every node must be
+/// defined exactly once, and every neighbour must be one of them.
+pub fn parse_network(input: &str) -> Result<(&str, Nodes<'_>), AocError> {
+    let blocks = blocks(input);
+    let Some((instructions_block, node_blocks)) = blocks.split_first() else {
+        return Err(AocError::missing("", "a list of instructions").at_line(1));
+    };
+    let (instructions_line_number, instructions_line) = instructions_block.first();
+    let instructions = instructions_line.trim();
+    parse_instructions(instructions).map_err(|e| e.within(instructions_line, instructions)
+                                                   .at_line(instructions_line_number))?;
+    if let Some((line_number, line)) = instructions_block.rest().first() {
+        return Err(AocError::unexpected(line, line, "a blank line").at_line(*line_number));
+    }
+    let mut entries = Vec::new();
+    let mut nodes = HashMap::new();
+    for (line_number, line) in node_blocks.iter().flat_map(|block| block.lines.iter()) {
+        let (node, left, right) = parse_line(line).map_err(|e| e.at_line(*line_number))?;
+        if nodes.insert(node, (left, right)).is_some() {
+            return Err(AocError::unexpected(line, node, "a node not already defined").at_line(*line_number));
+        }
+        entries.push((*line_number, *line, left, right));
+    }
+    for (line_number, line, left, right) in entries {
+        if let Some(undefined) = [left, right].into_iter().find(|name| !nodes.contains_key(name)) {
+            return Err(AocError::unexpected(line, undefined, "a defined node").at_line(line_number));
+        }
+    }
+    Ok((instructions, nodes))
+}
+
+
+/// Solve part 1: the number of steps to get from `AAA` to `ZZZ`.
+pub fn part1(input: &str) -> Result<u64, AocError> {
+    let network = Network::parse(input)?;
+    let start = network.id("AAA")?;
+    let end = network.id("ZZZ")?;
+    if !network.reachable_from(start)[end as usize] {
+        return Err(AocError::unsolvable("node `ZZZ` can't be reached from `AAA`"));
+    }
+    match network.walk_until(start, |node| node == end) {
+        Walk::Reached(num_steps) => Ok(num_steps),
+        Walk::Unreachable(Loop { tail, cycle }) => Err(AocError::unsolvable(format!(
+            "`ZZZ` is never reached: after {} steps the walk goes round a cycle of {} steps from `{}` forever",
+            tail, cycle.len(), network.name(cycle[0])))),
+    }
+}
+
+
+/// Solve part 2: the number of steps until ghosts starting on every node
+/// ending in `A` are all on nodes ending in `Z` at once.
+pub fn part2(input: &str) -> Result<u64, AocError> {
+    let network = Network::parse(input)?;
+    let mut is_end = vec![false; network.len()];
+    for id in network.ids_where(|name| name.ends_with('Z')) {
+        is_end[id as usize] = true;
+    }
+    let starts = network.ids_where(|name| name.ends_with('A'));
+    if starts.is_empty() {
+        return Err(AocError::unsolvable("no node ends in `A`"));
+    }
+    for start in &starts {
+        let reachable = network.reachable_from(*start);
+        if !reachable.iter().zip(&is_end).any(|(reachable, is_end)| *reachable && *is_end) {
+            return Err(AocError::unsolvable(format!("no node ending in `Z` can be reached from `{}`",
+                                                    network.name(*start))));
+        }
+    }
+    let ghosts: Vec<GhostCycle> = starts.into_iter()
+                                        .map(|start| GhostCycle::find(&network, start, |node| is_end[node as usize]))
+                                        .collect();
+    first_common_end(&ghosts).ok_or_else(|| AocError::unsolvable("the ghosts are never all on end nodes at once"))
+}
+
+
+/// Solve both parts of the puzzle.
+pub fn both_parts(input: &str) -> Result<(u64, u64), AocError> {
+    Ok((part1(input)?, part2(input)?))
+}
+
+
+/// The [`Solution`] for day 8.
+pub struct Day08;
+
+
+impl Solution for Day08 {
+    fn day(&self) -> u8 {
+        8
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AocError> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, AocError> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn it_works() {
+        let input =
+"RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)";
+        assert_eq!(both_parts(input), Ok((2, 2)));
+
+        let input =
+"LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+        assert_eq!(both_parts(input), Ok((6, 6)));
+
+        let input =
+"LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+        assert_eq!(part2(input), Ok(6));
+    }
+
+    #[test]
+    fn tolerant_parsing() {
+        let input = " LR \n\nstart=(  a1,end )\na1 = (start,end)\n  end = (end, end)";
+        let (instructions, nodes) = parse_network(input).unwrap();
+        assert_eq!(instructions, "LR");
+        assert_eq!(nodes["start"], ("a1", "end"));
+        let error = |input: &str| parse_network(input).unwrap_err().to_string();
+        assert_eq!(error("L\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)"),
+                   "line 3, column 8: expected a defined node, found `BBB`");
+        assert_eq!(error("L\n\nAAA = (A-A, ZZZ)"), "line 3, column 9: expected a letter or digit, found `-`");
+        assert_eq!(error("L\n\nAAA = BBB, ZZZ"), "line 3, column 7: expected `(`, found `B`");
+        assert_eq!(error("L\n\nAAA = (AAA, AAA) x"), "line 3, column 18: expected end of line, found `x`");
+        assert_eq!(error("L\n\nAAA = (AAA, AAA)\nAAA = (AAA, AAA)"),
+                   "line 4, column 1: expected a node not already defined, found `AAA`");
+        assert_eq!(part1("L\n\nAAA = (AAA, AAA)\nZZZ = (AAA, ZZZ)"),
+                   Err(AocError::unsolvable("node `ZZZ` can't be reached from `AAA`")));
+        assert_eq!(part2("L\n\nAAA = (AAA, AAA)\nZZZ = (AAA, ZZZ)"),
+                   Err(AocError::unsolvable("no node ending in `Z` can be reached from `AAA`")));
+    }
+
+    #[test]
+    fn endless_walk_is_reported() {
+        let input = "RL\n\nAAA = (BBB, CCC)\nBBB = (ZZZ, AAA)\nCCC = (AAA, DDD)\nDDD = (CCC, CCC)\nZZZ = (ZZZ, ZZZ)";
+        assert_eq!(part1(input), Err(AocError::unsolvable(
+            "`ZZZ` is never reached: after 0 steps the walk goes round a cycle of 2 steps from `AAA` forever")));
+    }
+
+    #[test]
+    fn bad_instruction_is_reported() {
+        let input =
+"LRX
+
+AAA = (ZZZ, ZZZ)";
+        assert_eq!(part1(input), Err(AocError::Unexpected {
+            line: 1,
+            column: 3,
+            text: "X".to_string(),
+            expected: "`L` or `R`".to_string(),
+        }));
+    }
+}
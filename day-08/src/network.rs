@@ -0,0 +1,258 @@
+//! A network with its nodes numbered, so walking it needs no string lookups.
+
+use std::collections::HashMap;
+
+use aoc_common::AocError;
+
+use crate::{parse_instructions, parse_network, Nodes};
+
+
+/// Which way to go from a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Take the first of the node's neighbours.
+    Left,
+    /// Take the second of the node's neighbours.
+    Right,
+}
+
+
+/// A walk that never gets where it was going: from `tail` steps on, it goes
+/// round the same cycle forever. As with [`Repeat`], the tail is a whole
+/// number of passes through the instructions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Loop {
+    /// The number of steps before the walk enters the cycle.
+    pub tail: u64,
+    /// The nodes the walk is on at each step of the cycle, starting with the
+    /// one it is on after `tail` steps. Nodes can appear more than once,
+    /// with different instructions to follow.
+    pub cycle: Vec<u32>,
+}
+
+
+/// How a walk repeats itself: from `tail` steps on, every `length` steps
+/// bring it back to the same node at the start of the instructions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Repeat {
+    /// The number of steps before the walk starts repeating. This is always
+    /// a whole number of passes through the instructions, so it can be a
+    /// little later than the first step of the cycle.
+    pub tail: u64,
+    /// The number of steps in each repeat, also a whole number of passes
+    /// through the instructions.
+    pub length: u64,
+    /// The node the walk is on at the start of each repeat.
+    pub node: u32,
+}
+
+
+/// Where a walk ended up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Walk {
+    /// The walk reached an end node after this many steps.
+    Reached(u64),
+    /// The walk came back to a node at the start of the instructions without
+    /// reaching an end node, so it never will.
+    Unreachable(Loop),
+}
+
+
+/// A network whose nodes are numbered from 0 in order of name, with its
+/// instructions decoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Network {
+    /// The name of each node, indexed by its id.
+    pub names: Vec<String>,
+    /// The ids of each node's left and right neighbours, indexed by its id
+    /// and then by [`Direction`].
+    pub adjacency: Vec<[u32; 2]>,
+    /// The instructions, which repeat forever.
+    pub instructions: Vec<Direction>,
+    ids: HashMap<String, u32>,
+}
+
+
+impl Network {
+    /// Parse the input into a network.
+    pub fn parse(input: &str) -> Result<Self, AocError> {
+        let (instructions, nodes) = parse_network(input)?;
+        Network::from_nodes(instructions, &nodes)
+    }
+
+    /// Number the nodes and decode the instructions. There must be at least
+    /// one instruction, each `L` or `R`, and every neighbour must be defined.
+    pub fn from_nodes(instructions: &str, nodes: &Nodes) -> Result<Self, AocError> {
+        let mut names: Vec<&str> = nodes.keys().copied().collect();
+        names.sort_unstable();
+        let ids: HashMap<String, u32> = names.iter()
+                                             .enumerate()
+                                             .map(|(id, name)| (name.to_string(), id as u32))
+                                             .collect();
+        let id = |name: &str| ids.get(name).copied().ok_or_else(|| undefined(name));
+        let adjacency = names.iter()
+                             .map(|name| {
+                                 let (left, right) = nodes[name];
+                                 Ok([id(left)?, id(right)?])
+                             })
+                             .collect::<Result<_, AocError>>()?;
+        let instructions = parse_instructions(instructions)?;
+        Ok(Network { names: names.into_iter().map(String::from).collect(), adjacency, instructions, ids })
+    }
+
+    /// The number of nodes.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Whether there are no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// The id of the node called `name`.
+    pub fn id(&self, name: &str) -> Result<u32, AocError> {
+        self.ids.get(name).copied().ok_or_else(|| undefined(name))
+    }
+
+    /// The name of the node with id `id`.
+    pub fn name(&self, id: u32) -> &str {
+        &self.names[id as usize]
+    }
+
+    /// The ids of the nodes whose names satisfy `predicate`, in order.
+    pub fn ids_where(&self, predicate: impl Fn(&str) -> bool) -> Vec<u32> {
+        (0..self.len() as u32).filter(|id| predicate(self.name(*id))).collect()
+    }
+
+    /// The node reached by going `direction` from `node`.
+    pub fn next(&self, node: u32, direction: Direction) -> u32 {
+        self.adjacency[node as usize][direction as usize]
+    }
+
+    /// Which nodes can be reached from `start` by some choice of
+    /// directions, indexed by id. `start` itself always can.
+    pub fn reachable_from(&self, start: u32) -> Vec<bool> {
+        let mut reachable = vec![false; self.len()];
+        reachable[start as usize] = true;
+        let mut to_visit = vec![start];
+        while let Some(node) = to_visit.pop() {
+            for neighbour in self.adjacency[node as usize] {
+                if !reachable[neighbour as usize] {
+                    reachable[neighbour as usize] = true;
+                    to_visit.push(neighbour);
+                }
+            }
+        }
+        reachable
+    }
+
+    /// Follow the instructions from `start` until reaching a node for which
+    /// `is_end` holds, or until the walk repeats itself.
+    pub fn walk_until(&self, start: u32, is_end: impl Fn(u32) -> bool) -> Walk {
+        let mut reached = None;
+        let repeat = self.repeat_from(start, |step, node| {
+            if is_end(node) {
+                reached = Some(step);
+            }
+            reached.is_none()
+        });
+        match repeat {
+            // nothing is kept while walking, so walk the cycle again to list
+            // its nodes
+            Some(Repeat { tail, length, node }) => {
+                let cycle = std::iter::once(node).chain(self.walk(node)).take(length as usize).collect();
+                Walk::Unreachable(Loop { tail, cycle })
+            }
+            None => Walk::Reached(reached.expect("the walk only stops early at an end")),
+        }
+    }
+
+    /// Follow the instructions from `start` until the walk repeats itself,
+    /// calling `visit` with each step number and the node the walk is on
+    /// then. The walk stops early, returning `None`, as soon as `visit`
+    /// returns false.
+    pub fn repeat_from(&self, start: u32, mut visit: impl FnMut(u64, u32) -> bool) -> Option<Repeat> {
+        let num_instructions = self.instructions.len() as u64;
+        // being back on a node at the start of the instructions means
+        // everything from then on happens again, so only those steps need
+        // remembering, which takes a slot per node rather than per state
+        let mut seen_at_start = vec![None; self.len()];
+        let mut node = start;
+        let mut step = 0;
+        loop {
+            let instruction_index = (step % num_instructions) as usize;
+            if instruction_index == 0 {
+                if let Some(tail) = seen_at_start[node as usize] {
+                    return Some(Repeat { tail, length: step - tail, node });
+                }
+                seen_at_start[node as usize] = Some(step);
+            }
+            if !visit(step, node) {
+                return None;
+            }
+            node = self.next(node, self.instructions[instruction_index]);
+            step += 1;
+        }
+    }
+
+    /// The nodes visited by following the instructions from `start`, not
+    /// including `start` itself. This never ends.
+    pub fn walk(&self, start: u32) -> impl Iterator<Item = u32> + '_ {
+        self.instructions.iter().cycle().scan(start, |node, direction| {
+            *node = self.next(*node, *direction);
+            Some(*node)
+        })
+    }
+}
+
+
+fn undefined(name: &str) -> AocError {
+    AocError::unsolvable(format!("node `{}` is not defined", name))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nodes_are_numbered() {
+        let network = Network::parse("LLR\n\nBBB = (AAA, ZZZ)\nAAA = (BBB, BBB)\nZZZ = (ZZZ, ZZZ)").unwrap();
+        assert_eq!(network.names, vec!["AAA", "BBB", "ZZZ"]);
+        assert_eq!(network.adjacency, vec![[1, 1], [0, 2], [2, 2]]);
+        assert_eq!(network.instructions, vec![Direction::Left, Direction::Left, Direction::Right]);
+        assert_eq!(network.walk(0).take(7).collect::<Vec<_>>(), vec![1, 0, 1, 0, 1, 2, 2]);
+        assert_eq!(network.ids_where(|name| name.ends_with('Z')), vec![2]);
+        assert_eq!(network.reachable_from(2), vec![false, false, true]);
+        assert_eq!(network.walk_until(0, |node| node == 2), Walk::Reached(6));
+        assert_eq!(network.walk_until(0, |node| node == 3), Walk::Unreachable(Loop { tail: 6, cycle: vec![2, 2, 2] }));
+    }
+
+    #[test]
+    fn instructions_are_checked() {
+        let nodes = Nodes::from([("AAA", ("AAA", "AAA"))]);
+        assert_eq!(Network::from_nodes("", &nodes), Err(AocError::Missing {
+            line: 0,
+            column: 1,
+            expected: "a list of instructions".to_string(),
+        }));
+        assert_eq!(Network::from_nodes("LX", &nodes).unwrap_err().column(), 2);
+        assert_eq!(Network::from_nodes("RL", &nodes).unwrap().instructions, vec![Direction::Right, Direction::Left]);
+    }
+
+    #[test]
+    fn long_instructions_on_a_large_network() {
+        // a table per (node, instruction) state would need billions of
+        // entries here
+        let nodes: Vec<String> = (0..20_000).map(|i| format!("N{} = (N{}, N{})", i, (i + 1) % 20_000, i)).collect();
+        let input = format!("{}\n\n{}", "L".repeat(20_000), nodes.join("\n"));
+        let network = Network::parse(&input).unwrap();
+        let [start, end] = [network.id("N0").unwrap(), network.id("N5").unwrap()];
+        assert_eq!(network.walk_until(start, |node| node == end), Walk::Reached(5));
+        assert_eq!(network.walk_until(start, |_| false), Walk::Unreachable(Loop {
+            tail: 0,
+            cycle: std::iter::once(start).chain(network.walk(start)).take(20_000).collect(),
+        }));
+    }
+}